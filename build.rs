@@ -0,0 +1,27 @@
+/// embeds the commit being built, when building from a git checkout
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    let Some(root) = std::env::var_os("CARGO_MANIFEST_DIR") else {
+        return;
+    };
+    let root = std::path::PathBuf::from(root);
+    let git = root.join(".git");
+    // a packaged crate has no checkout, nor any commit of its own to embed
+    if !git.exists() {
+        return;
+    }
+    println!("cargo:rerun-if-changed={}", git.join("HEAD").display());
+    println!("cargo:rerun-if-changed={}", git.join("refs").display());
+    let commit = std::process::Command::new("git")
+        .arg("-C")
+        .arg(&root)
+        .args(["rev-parse", "--short", "HEAD"])
+        .output();
+    if let Ok(output) = commit {
+        if output.status.success() {
+            if let Ok(commit) = String::from_utf8(output.stdout) {
+                println!("cargo:rustc-env=ORHFETCH_COMMIT={}", commit.trim());
+            }
+        }
+    }
+}
@@ -0,0 +1,384 @@
+/* # message catalog */
+
+/// the fetch's own words by language, left out where they read the same as in english;
+/// counted nouns come in a singular and a plural, so languages with more plural forms
+/// than that keep them in english
+const CATALOG: &[(&str, &[(&str, &str)])] = &[
+    (
+        "ar",
+        &[
+            ("Hardware", "العتاد"),
+            ("Software", "البرمجيات"),
+            ("System", "النظام"),
+            ("Desktop", "سطح المكتب"),
+            ("Network", "الشبكة"),
+            ("update available", "يتوفر تحديث"),
+            ("compression", "الضغط"),
+            ("encryption", "التشفير"),
+            ("idle", "الخمول"),
+            ("mitigations", "التخفيفات"),
+            ("orphans", "الحزم اليتيمة"),
+            ("remaining", "متبقية"),
+            ("until full", "حتى الامتلاء"),
+            ("framework", "إطار العمل"),
+            ("userland", "فضاء المستخدم"),
+            ("active", "نشط"),
+            ("inactive", "غير نشط"),
+            ("charging", "قيد الشحن"),
+            ("this month", "هذا الشهر"),
+            ("since install", "منذ التثبيت"),
+            ("record", "الرقم القياسي"),
+            ("healthy", "سليمة"),
+            ("tight", "محدودة"),
+            (
+                "under pressure (consider more RAM)",
+                "تحت الضغط (فكّر في ذاكرة أكبر)",
+            ),
+            ("total VRAM", "إجمالي VRAM"),
+        ],
+    ),
+    (
+        "de",
+        &[
+            ("Network", "Netzwerk"),
+            ("update available", "Update verfügbar"),
+            ("compression", "Komprimierung"),
+            ("encryption", "Verschlüsselung"),
+            ("idle", "Leerlauf"),
+            ("mitigations", "Gegenmaßnahmen"),
+            ("orphans", "Waisen"),
+            ("remaining", "verbleibend"),
+            ("until full", "bis voll"),
+            ("kernel", "Kernel"),
+            ("kernels", "Kernel"),
+            ("thread", "Thread"),
+            ("threads", "Threads"),
+            ("mirror", "Spiegelserver"),
+            ("mirrors", "Spiegelserver"),
+            ("font", "Schriftart"),
+            ("fonts", "Schriftarten"),
+            ("pci device", "PCI-Gerät"),
+            ("pci devices", "PCI-Geräte"),
+            ("session", "Sitzung"),
+            ("sessions", "Sitzungen"),
+            ("console", "Konsole"),
+            ("consoles", "Konsolen"),
+            ("service", "Dienst"),
+            ("services", "Dienste"),
+            ("active", "aktiv"),
+            ("inactive", "inaktiv"),
+            ("charging", "lädt"),
+            ("this month", "diesen Monat"),
+            ("since install", "seit der Installation"),
+            ("record", "Rekord"),
+            ("healthy", "gesund"),
+            ("tight", "knapp"),
+            (
+                "under pressure (consider more RAM)",
+                "unter Druck (mehr RAM erwägen)",
+            ),
+            ("total VRAM", "VRAM insgesamt"),
+            ("display", "Bildschirm"),
+            ("displays", "Bildschirme"),
+            ("rule", "Regel"),
+            ("rules", "Regeln"),
+        ],
+    ),
+    (
+        "es",
+        &[
+            ("System", "Sistema"),
+            ("Desktop", "Escritorio"),
+            ("Network", "Red"),
+            ("update available", "actualización disponible"),
+            ("compression", "compresión"),
+            ("encryption", "cifrado"),
+            ("idle", "reposo"),
+            ("mitigations", "mitigaciones"),
+            ("orphans", "huérfanos"),
+            ("remaining", "restante"),
+            ("until full", "hasta completar"),
+            ("kernel", "núcleo"),
+            ("kernels", "núcleos"),
+            ("thread", "hilo"),
+            ("threads", "hilos"),
+            ("mirror", "réplica"),
+            ("mirrors", "réplicas"),
+            ("font", "fuente"),
+            ("fonts", "fuentes"),
+            ("pci device", "dispositivo pci"),
+            ("pci devices", "dispositivos pci"),
+            ("session", "sesión"),
+            ("sessions", "sesiones"),
+            ("console", "consola"),
+            ("consoles", "consolas"),
+            ("service", "servicio"),
+            ("services", "servicios"),
+            ("framework", "entorno"),
+            ("userland", "espacio de usuario"),
+            ("active", "activo"),
+            ("inactive", "inactivo"),
+            ("charging", "cargando"),
+            ("this month", "este mes"),
+            ("since install", "desde la instalación"),
+            ("record", "récord"),
+            ("healthy", "saludable"),
+            ("tight", "justa"),
+            (
+                "under pressure (consider more RAM)",
+                "bajo presión (considera más RAM)",
+            ),
+            ("total VRAM", "de VRAM en total"),
+            ("display", "pantalla"),
+            ("displays", "pantallas"),
+            ("rule", "regla"),
+            ("rules", "reglas"),
+        ],
+    ),
+    (
+        "fr",
+        &[
+            ("Hardware", "Matériel"),
+            ("Software", "Logiciel"),
+            ("System", "Système"),
+            ("Desktop", "Bureau"),
+            ("Network", "Réseau"),
+            ("update available", "mise à jour disponible"),
+            ("encryption", "chiffrement"),
+            ("idle", "veille"),
+            ("mitigations", "atténuations"),
+            ("orphans", "orphelins"),
+            ("remaining", "restant"),
+            ("until full", "avant la charge complète"),
+            ("kernel", "noyau"),
+            ("kernels", "noyaux"),
+            ("mirror", "miroir"),
+            ("mirrors", "miroirs"),
+            ("font", "police"),
+            ("fonts", "polices"),
+            ("pci device", "périphérique pci"),
+            ("pci devices", "périphériques pci"),
+            ("userland", "espace utilisateur"),
+            ("active", "actif"),
+            ("inactive", "inactif"),
+            ("charging", "en charge"),
+            ("this month", "ce mois-ci"),
+            ("since install", "depuis l'installation"),
+            ("healthy", "saine"),
+            ("tight", "juste"),
+            (
+                "under pressure (consider more RAM)",
+                "sous pression (envisagez plus de RAM)",
+            ),
+            ("total VRAM", "de VRAM au total"),
+            ("display", "écran"),
+            ("displays", "écrans"),
+            ("rule", "règle"),
+            ("rules", "règles"),
+        ],
+    ),
+    (
+        "it",
+        &[
+            ("System", "Sistema"),
+            ("Network", "Rete"),
+            ("update available", "aggiornamento disponibile"),
+            ("compression", "compressione"),
+            ("encryption", "cifratura"),
+            ("idle", "inattività"),
+            ("mitigations", "mitigazioni"),
+            ("orphans", "orfani"),
+            ("remaining", "rimanenti"),
+            ("until full", "alla carica completa"),
+            ("kernels", "kernel"),
+            ("threads", "thread"),
+            ("mirrors", "mirror"),
+            ("fonts", "font"),
+            ("pci device", "dispositivo pci"),
+            ("pci devices", "dispositivi pci"),
+            ("session", "sessione"),
+            ("sessions", "sessioni"),
+            ("consoles", "console"),
+            ("service", "servizio"),
+            ("services", "servizi"),
+            ("userland", "spazio utente"),
+            ("active", "attivo"),
+            ("inactive", "inattivo"),
+            ("charging", "in carica"),
+            ("this month", "questo mese"),
+            ("since install", "dall'installazione"),
+            ("healthy", "in salute"),
+            ("tight", "scarsa"),
+            (
+                "under pressure (consider more RAM)",
+                "sotto pressione (valuta più RAM)",
+            ),
+            ("total VRAM", "di VRAM in totale"),
+            ("display", "schermo"),
+            ("displays", "schermi"),
+            ("rule", "regola"),
+            ("rules", "regole"),
+        ],
+    ),
+    (
+        "nl",
+        &[
+            ("System", "Systeem"),
+            ("Desktop", "Bureaublad"),
+            ("Network", "Netwerk"),
+            ("update available", "update beschikbaar"),
+            ("compression", "compressie"),
+            ("encryption", "versleuteling"),
+            ("idle", "inactief"),
+            ("mitigations", "mitigaties"),
+            ("orphans", "wezen"),
+            ("remaining", "resterend"),
+            ("until full", "tot vol"),
+            ("mirror", "spiegel"),
+            ("mirrors", "spiegels"),
+            ("font", "lettertype"),
+            ("fonts", "lettertypen"),
+            ("pci device", "pci-apparaat"),
+            ("pci devices", "pci-apparaten"),
+            ("session", "sessie"),
+            ("sessions", "sessies"),
+            ("service", "dienst"),
+            ("services", "diensten"),
+            ("active", "actief"),
+            ("inactive", "inactief"),
+            ("charging", "laadt op"),
+            ("this month", "deze maand"),
+            ("since install", "sinds installatie"),
+            ("healthy", "gezond"),
+            ("tight", "krap"),
+            (
+                "under pressure (consider more RAM)",
+                "onder druk (overweeg meer RAM)",
+            ),
+            ("total VRAM", "VRAM in totaal"),
+            ("display", "scherm"),
+            ("displays", "schermen"),
+            ("rule", "regel"),
+            ("rules", "regels"),
+        ],
+    ),
+    (
+        "pl",
+        &[
+            ("Hardware", "Sprzęt"),
+            ("Software", "Oprogramowanie"),
+            ("Desktop", "Pulpit"),
+            ("Network", "Sieć"),
+            ("update available", "dostępna aktualizacja"),
+            ("compression", "kompresja"),
+            ("encryption", "szyfrowanie"),
+            ("idle", "bezczynność"),
+            ("mitigations", "środki zaradcze"),
+            ("orphans", "osierocone"),
+            ("remaining", "pozostało"),
+            ("until full", "do pełna"),
+            ("userland", "przestrzeń użytkownika"),
+            ("active", "aktywny"),
+            ("inactive", "nieaktywny"),
+            ("charging", "ładowanie"),
+            ("this month", "w tym miesiącu"),
+            ("since install", "od instalacji"),
+            ("record", "rekord"),
+            ("healthy", "w porządku"),
+            ("tight", "na styk"),
+            (
+                "under pressure (consider more RAM)",
+                "pod presją (rozważ więcej RAM)",
+            ),
+            ("total VRAM", "VRAM łącznie"),
+        ],
+    ),
+    (
+        "pt",
+        &[
+            ("System", "Sistema"),
+            ("Desktop", "Área de trabalho"),
+            ("Network", "Rede"),
+            ("update available", "atualização disponível"),
+            ("compression", "compressão"),
+            ("encryption", "criptografia"),
+            ("idle", "ocioso"),
+            ("mitigations", "mitigações"),
+            ("orphans", "órfãos"),
+            ("remaining", "restante"),
+            ("until full", "até completar"),
+            ("kernel", "núcleo"),
+            ("kernels", "núcleos"),
+            ("mirror", "espelho"),
+            ("mirrors", "espelhos"),
+            ("font", "fonte"),
+            ("fonts", "fontes"),
+            ("pci device", "dispositivo pci"),
+            ("pci devices", "dispositivos pci"),
+            ("session", "sessão"),
+            ("sessions", "sessões"),
+            ("service", "serviço"),
+            ("services", "serviços"),
+            ("userland", "espaço do usuário"),
+            ("active", "ativo"),
+            ("inactive", "inativo"),
+            ("charging", "carregando"),
+            ("this month", "este mês"),
+            ("since install", "desde a instalação"),
+            ("record", "recorde"),
+            ("healthy", "saudável"),
+            ("tight", "apertada"),
+            (
+                "under pressure (consider more RAM)",
+                "sob pressão (considere mais RAM)",
+            ),
+            ("total VRAM", "de VRAM no total"),
+            ("display", "tela"),
+            ("displays", "telas"),
+            ("rule", "regra"),
+            ("rules", "regras"),
+        ],
+    ),
+    (
+        "ru",
+        &[
+            ("Hardware", "Оборудование"),
+            ("Software", "Программы"),
+            ("System", "Система"),
+            ("Desktop", "Рабочий стол"),
+            ("Network", "Сеть"),
+            ("update available", "доступно обновление"),
+            ("compression", "сжатие"),
+            ("encryption", "шифрование"),
+            ("idle", "простой"),
+            ("mitigations", "меры защиты"),
+            ("orphans", "сироты"),
+            ("remaining", "осталось"),
+            ("until full", "до полной зарядки"),
+            ("framework", "фреймворк"),
+            ("userland", "пространство пользователя"),
+            ("active", "активен"),
+            ("inactive", "неактивен"),
+            ("charging", "заряжается"),
+            ("this month", "в этом месяце"),
+            ("since install", "с установки"),
+            ("record", "рекорд"),
+            ("healthy", "в норме"),
+            ("tight", "впритык"),
+            (
+                "under pressure (consider more RAM)",
+                "под нагрузкой (добавьте RAM)",
+            ),
+            ("total VRAM", "VRAM всего"),
+        ],
+    ),
+];
+
+/// `text` in `language`, or as it is when the catalog has nothing for it
+pub fn translate(language: &str, text: &'static str) -> &'static str {
+    CATALOG
+        .iter()
+        .find(|(code, _)| *code == language)
+        .and_then(|(_, messages)| messages.iter().find(|(english, _)| *english == text))
+        .map_or(text, |(_, translated)| translated)
+}
@@ -0,0 +1,732 @@
+use std::io::IsTerminal;
+
+use crate::data::{
+    hostname_rgb, is_laptop, is_rtl_locale, locale_language, rgb_escape, HostnameLayout, Style,
+    TemperatureUnit, UptimePeriod, ACCENT, PALETTE,
+};
+use crate::fields::{default_fields, find_field};
+use crate::render::recolour;
+
+type ConfigResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+/* # configuration */
+
+/// whether escapes are written, `auto` meaning only to a terminal without `NO_COLOR`
+#[derive(Clone, Copy)]
+pub enum ColourMode {
+    Always,
+    Auto,
+    Never,
+}
+
+/// how the fields are arranged
+#[derive(Clone, Copy)]
+pub enum Layout {
+    Plain,
+    Tree,
+}
+
+pub struct Config {
+    pub fields: Vec<String>,
+    /// print the terminal colour swatches under the fields
+    pub colours: bool,
+    /// number each swatch with its palette index
+    pub colour_labels: bool,
+    pub colour: ColourMode,
+    /// keep nerd font glyphs even when stdout is not a terminal
+    pub force_icons: bool,
+    /// swap nerd font glyphs for emoji wherever one fits
+    pub emoji_icons: bool,
+    /// show the maker's logo on the cpu and gpu fields
+    pub vendor_icons: bool,
+    /// palette index, `#rrggbb` or `hash` for icons and the hostname
+    pub accent: Option<String>,
+    /// put the icons on a stripe of the accent colour
+    pub stripe: bool,
+    /// follow readings like disk use with a green, yellow or red circle
+    pub status_emoji: bool,
+    /// language to translate headers into in place of the locale's
+    pub language: Option<String>,
+    /// mirror each line and right-align the fetch, on by default in rtl locales
+    pub rtl: bool,
+    /// base16 scheme or Xresources file to take the palette from
+    pub theme: Option<std::path::PathBuf>,
+    /// text or ansi art file to print beside the fetch
+    pub logo: Option<std::path::PathBuf>,
+    /// move the logo's colours onto the palette in use
+    pub recolour_logo: bool,
+    pub layout: Layout,
+    pub hostname: HostnameLayout,
+    /// what every field line starts with
+    pub prefix: String,
+    /// line printed under the fields, `%version%` and `%commit%` filled in
+    pub footer: Option<String>,
+    /// field whose value is printed as a qr code after the fetch
+    pub qr: Option<String>,
+    /// type the fetch out a character at a time on a terminal
+    pub animate: bool,
+    /// print nothing but the bare values, one per line
+    pub values_only: bool,
+    /// squeeze the values onto one line, like `[ Arch | 6.5 | 3d ]`
+    pub badge: bool,
+    /// print `name: value` lines sorted by name, for diffing machines
+    pub canonical: bool,
+    /// print the fetch as a json object, one per refresh when watching
+    pub json: bool,
+    /// write the fetch to this svg file instead of stdout
+    pub svg: Option<std::path::PathBuf>,
+    /// write the fetch to this png file instead of stdout
+    pub png: Option<std::path::PathBuf>,
+    /// redact the hostname, username and addresses for sharing
+    pub anon: bool,
+    /// fields whose absence from the fetch makes orhfetch exit unsuccessfully
+    pub critical: Vec<String>,
+    /// report how long each field took on stderr
+    pub timings: bool,
+    /// mention newer releases on crates.io
+    pub check_updates: bool,
+    /// run the scripts in the plugin directory as extra fields
+    pub plugins: bool,
+    /// shell commands each named field's value is piped through, as `filter_os`
+    pub filters: Vec<(String, String)>,
+    /// time between refreshes of a fetch that keeps running
+    pub watch: Option<core::time::Duration>,
+    /// refresh by rewriting just the changed volatile lines instead of the whole screen
+    pub in_place: bool,
+    /// print the version, and commit when built from git, instead of fetching
+    pub version: bool,
+    /// print the resolved configuration as toml instead of fetching
+    pub dump_config: bool,
+    /// count uptime from this file's modification time instead of boot
+    pub uptime_since: Option<std::path::PathBuf>,
+    /// show uptime as a share of the month or of the time since install
+    pub uptime_period: Option<UptimePeriod>,
+    /// keep the longest uptime in the cache directory and show it too
+    pub uptime_record: bool,
+    pub temperature_unit: TemperatureUnit,
+    /// name the distro a derivative is based on after its own
+    pub os_base: bool,
+    /// break memory down into used, free and cached
+    pub memory_verbose: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            fields: default_fields()
+                .iter()
+                .map(|&name| name.to_owned())
+                .collect(),
+            colours: true,
+            colour_labels: false,
+            colour: ColourMode::Auto,
+            force_icons: false,
+            emoji_icons: false,
+            vendor_icons: false,
+            accent: None,
+            stripe: false,
+            status_emoji: false,
+            language: None,
+            rtl: is_rtl_locale(),
+            theme: None,
+            logo: None,
+            recolour_logo: false,
+            layout: Layout::Plain,
+            hostname: HostnameLayout::Full,
+            prefix: " ".to_owned(),
+            footer: None,
+            qr: None,
+            animate: false,
+            values_only: false,
+            badge: false,
+            canonical: false,
+            json: false,
+            svg: None,
+            png: None,
+            anon: false,
+            critical: Vec::new(),
+            timings: false,
+            check_updates: false,
+            plugins: true,
+            filters: Vec::new(),
+            watch: None,
+            in_place: false,
+            version: false,
+            dump_config: false,
+            uptime_since: None,
+            uptime_period: None,
+            uptime_record: false,
+            temperature_unit: TemperatureUnit::Celsius,
+            os_base: false,
+            memory_verbose: false,
+        }
+    }
+}
+
+/// options which need no value on the command line
+const SWITCHES: &[&str] = &[
+    "colours",
+    "colour_labels",
+    "force_colour",
+    "force_icons",
+    "emoji_icons",
+    "vendor_icons",
+    "stripe",
+    "status_emoji",
+    "recolour_logo",
+    "rtl",
+    "animate",
+    "values_only",
+    "badge",
+    "canonical",
+    "json",
+    "anon",
+    "timings",
+    "check_updates",
+    "plugins",
+    "in_place",
+    "uptime_record",
+    "os_base",
+    "memory_verbose",
+    "version",
+    "dump_config",
+];
+
+fn parse_bool(key: &str, value: &str) -> ConfigResult<bool> {
+    match value {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        _ => simple_error::bail!("{} expects true or false", key),
+    }
+}
+
+fn platform_matches(condition: &str) -> ConfigResult<bool> {
+    Ok(match condition {
+        "linux" => nix::sys::utsname::uname().sysname() == "Linux",
+        "macos" => nix::sys::utsname::uname().sysname() == "Darwin",
+        "laptop" => is_laptop(),
+        "desktop" => !is_laptop(),
+        _ => simple_error::bail!("unrecognised platform {}", condition),
+    })
+}
+
+/// a field may carry conditions like `battery@laptop` or `mesa@linux@desktop`,
+/// and is left out wherever any of them does not hold; the hostname leads
+/// unless placed somewhere
+fn parse_fields(list: &str) -> ConfigResult<Vec<String>> {
+    let mut fields = Vec::new();
+    for entry in list
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        let mut conditions = entry.split('@');
+        let name = conditions.next().unwrap_or_default();
+        if find_field(name).is_none() {
+            simple_error::bail!("unrecognised field {}", name);
+        }
+        if conditions
+            .map(platform_matches)
+            .collect::<ConfigResult<Vec<bool>>>()?
+            .into_iter()
+            .all(|matches| matches)
+        {
+            fields.push(name.to_owned());
+        }
+    }
+    if !list
+        .split(',')
+        .any(|entry| entry.trim().split('@').next() == Some("hostname"))
+    {
+        fields.insert(0, "hostname".to_owned());
+    }
+    Ok(fields)
+}
+
+impl Config {
+    /// defaults, overridden by the config file, overridden by the command line
+    pub fn load() -> ConfigResult<Self> {
+        let mut config = Self::default();
+        config.apply_file()?;
+        config.apply_args()?;
+        // the code would land in the middle of the json stream
+        if config.qr.is_some() && config.json {
+            simple_error::bail!("qr cannot be combined with json");
+        }
+        Ok(config)
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> ConfigResult<()> {
+        match key {
+            "fields" => self.fields = parse_fields(value)?,
+            "critical" => {
+                self.critical = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(|name| match find_field(name) {
+                        Some(field) => Ok(field.name.to_owned()),
+                        None => simple_error::bail!("unrecognised field {}", name),
+                    })
+                    .collect::<ConfigResult<Vec<String>>>()?;
+            }
+            "colours" => self.colours = parse_bool(key, value)?,
+            "colour_labels" => self.colour_labels = parse_bool(key, value)?,
+            "colour" => {
+                self.colour = match value {
+                    "always" => ColourMode::Always,
+                    "auto" => ColourMode::Auto,
+                    "never" => ColourMode::Never,
+                    _ => simple_error::bail!("{} expects always, auto or never", key),
+                }
+            }
+            "force_colour" => {
+                self.colour = if parse_bool(key, value)? {
+                    ColourMode::Always
+                } else {
+                    ColourMode::Auto
+                }
+            }
+            "force_icons" => self.force_icons = parse_bool(key, value)?,
+            "emoji_icons" => self.emoji_icons = parse_bool(key, value)?,
+            "vendor_icons" => self.vendor_icons = parse_bool(key, value)?,
+            "accent" => self.accent = Some(value.to_owned()),
+            "stripe" => self.stripe = parse_bool(key, value)?,
+            "status_emoji" => self.status_emoji = parse_bool(key, value)?,
+            "language" => self.language = Some(value.to_owned()),
+            "rtl" => self.rtl = parse_bool(key, value)?,
+            "theme" => self.theme = Some(value.into()),
+            "logo" => self.logo = Some(value.into()),
+            "recolour_logo" => self.recolour_logo = parse_bool(key, value)?,
+            "layout" => {
+                self.layout = match value {
+                    "plain" => Layout::Plain,
+                    "tree" => Layout::Tree,
+                    _ => simple_error::bail!("{} expects plain or tree", key),
+                }
+            }
+            "hostname" => {
+                self.hostname = match value {
+                    "full" => HostnameLayout::Full,
+                    "split" => HostnameLayout::Split,
+                    "host" => HostnameLayout::Host,
+                    _ => simple_error::bail!("{} expects full, split or host", key),
+                }
+            }
+            "prefix" => self.prefix = value.to_owned(),
+            "footer" => self.footer = Some(value.to_owned()),
+            "qr" => {
+                if find_field(value).is_none() {
+                    simple_error::bail!("unrecognised field {}", value);
+                }
+                self.qr = Some(value.to_owned());
+            }
+            "animate" => self.animate = parse_bool(key, value)?,
+            "values_only" => self.values_only = parse_bool(key, value)?,
+            "badge" => self.badge = parse_bool(key, value)?,
+            "canonical" => self.canonical = parse_bool(key, value)?,
+            "json" => self.json = parse_bool(key, value)?,
+            "svg" => self.svg = Some(value.into()),
+            "png" => self.png = Some(value.into()),
+            "anon" => self.anon = parse_bool(key, value)?,
+            "timings" => self.timings = parse_bool(key, value)?,
+            "check_updates" => self.check_updates = parse_bool(key, value)?,
+            "plugins" => self.plugins = parse_bool(key, value)?,
+            "watch" => {
+                const DAY: core::time::Duration = core::time::Duration::from_secs(24 * 60 * 60);
+                self.watch = match value
+                    .parse::<f64>()
+                    .map(core::time::Duration::try_from_secs_f64)
+                {
+                    Ok(Ok(interval)) if !interval.is_zero() && interval <= DAY => Some(interval),
+                    _ => simple_error::bail!("{} expects a number of seconds up to a day", key),
+                }
+            }
+            "in_place" => self.in_place = parse_bool(key, value)?,
+            "version" => self.version = parse_bool(key, value)?,
+            "dump_config" => self.dump_config = parse_bool(key, value)?,
+            "uptime_since" => self.uptime_since = Some(value.into()),
+            "uptime_record" => self.uptime_record = parse_bool(key, value)?,
+            "os_base" => self.os_base = parse_bool(key, value)?,
+            "memory_verbose" => self.memory_verbose = parse_bool(key, value)?,
+            "temperature_unit" => {
+                self.temperature_unit = match value {
+                    "c" | "celsius" => TemperatureUnit::Celsius,
+                    "f" | "fahrenheit" => TemperatureUnit::Fahrenheit,
+                    _ => simple_error::bail!("{} expects celsius or fahrenheit", key),
+                }
+            }
+            "uptime_period" => {
+                self.uptime_period = match value {
+                    "month" => Some(UptimePeriod::Month),
+                    "install" => Some(UptimePeriod::Install),
+                    _ => simple_error::bail!("{} expects month or install", key),
+                }
+            }
+            _ => match key.strip_prefix("filter_") {
+                Some(field) if find_field(field).is_some() => {
+                    self.filters.retain(|(name, _)| name != field);
+                    self.filters.push((field.to_owned(), value.to_owned()));
+                }
+                _ => simple_error::bail!("unrecognised option {}", key),
+            },
+        }
+        Ok(())
+    }
+
+    pub fn style(&self) -> ConfigResult<Style> {
+        let terminal = std::io::stdout().is_terminal();
+        let palette: Vec<String> = match &self.theme {
+            Some(theme) => read_theme(theme)?.into_iter().map(rgb_escape).collect(),
+            None => PALETTE.iter().map(|&colour| colour.to_owned()).collect(),
+        };
+        let accent = match self.accent.as_deref() {
+            None => palette[ACCENT].clone(),
+            Some("hash") => rgb_escape(hostname_rgb()?),
+            Some(accent) => match accent.parse::<usize>() {
+                Ok(index) if index < palette.len() => palette[index].clone(),
+                _ => rgb_escape(parse_rgb(accent).ok_or_else(|| {
+                    simple_error::simple_error!("unrecognised accent {}", accent)
+                })?),
+            },
+        };
+        let logo = match &self.logo {
+            Some(logo) => std::fs::read_to_string(logo)?
+                .lines()
+                .map(|art| {
+                    if self.recolour_logo {
+                        recolour(art, &palette)
+                    } else {
+                        art.to_owned()
+                    }
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        Ok(Style {
+            colour: match self.colour {
+                ColourMode::Always => true,
+                ColourMode::Auto => terminal && std::env::var_os("NO_COLOR").is_none(),
+                ColourMode::Never => false,
+            },
+            icons: self.force_icons || terminal,
+            emoji: self.emoji_icons,
+            prefix: self.prefix.clone(),
+            accent,
+            stripe: self.stripe,
+            rtl: self.rtl,
+            logo,
+            status_emoji: self.status_emoji,
+            language: self.language.clone().unwrap_or_else(locale_language),
+            palette,
+        })
+    }
+}
+
+/* # config file */
+
+pub fn config_dir() -> ConfigResult<std::path::PathBuf> {
+    Ok(match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(config) => std::path::PathBuf::from(config),
+        None => std::path::PathBuf::from(std::env::var("HOME")?).join(".config"),
+    }
+    .join("orhfetch"))
+}
+
+fn config_path() -> ConfigResult<std::path::PathBuf> {
+    Ok(config_dir()?.join("config.toml"))
+}
+
+/// the part of a line before any comment, minding `#` inside strings
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (index, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..index],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn parse_string(raw: &str) -> ConfigResult<String> {
+    Ok(raw
+        .strip_prefix('"')
+        .and_then(|raw| raw.strip_suffix('"'))
+        .ok_or_else(|| simple_error::simple_error!("unterminated string {}", raw))?
+        .replace("\\\"", "\"")
+        .replace("\\\\", "\\"))
+}
+
+/// flattens a toml value into the same textual form the command line uses
+fn parse_value(raw: &str) -> ConfigResult<String> {
+    if let Some(items) = raw.strip_prefix('[').and_then(|raw| raw.strip_suffix(']')) {
+        Ok(items
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(parse_string)
+            .collect::<ConfigResult<Vec<String>>>()?
+            .join(","))
+    } else if raw.starts_with('"') {
+        parse_string(raw)
+    } else {
+        Ok(raw.to_owned())
+    }
+}
+
+impl Config {
+    /// reads the small subset of toml orhfetch needs: `key = value` lines
+    /// holding strings, booleans, numbers and single-line arrays of strings
+    fn apply_file(&mut self) -> ConfigResult<()> {
+        let path = config_path()?;
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(error) => return Err(error.into()),
+        };
+
+        for (number, line) in contents.lines().enumerate() {
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (key, value) = line.split_once('=').ok_or_else(|| {
+                simple_error::simple_error!(
+                    "{}:{}: expected key = value",
+                    path.display(),
+                    number + 1
+                )
+            })?;
+            self.set(key.trim(), &parse_value(value.trim())?)
+                .map_err(|error| format!("{}:{}: {}", path.display(), number + 1, error))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+impl Config {
+    /// every option as it ended up, in the form the config file takes,
+    /// leaving out the unset ones
+    pub fn to_toml(&self) -> String {
+        let path = |path: &Option<std::path::PathBuf>| {
+            path.as_ref().map(|path| quote(&path.to_string_lossy()))
+        };
+        let options = [
+            (
+                "fields",
+                Some(format!(
+                    "[{}]",
+                    self.fields
+                        .iter()
+                        .map(|name| quote(name))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                )),
+            ),
+            ("colours", Some(self.colours.to_string())),
+            ("colour_labels", Some(self.colour_labels.to_string())),
+            (
+                "colour",
+                Some(quote(match self.colour {
+                    ColourMode::Always => "always",
+                    ColourMode::Auto => "auto",
+                    ColourMode::Never => "never",
+                })),
+            ),
+            ("force_icons", Some(self.force_icons.to_string())),
+            ("emoji_icons", Some(self.emoji_icons.to_string())),
+            ("vendor_icons", Some(self.vendor_icons.to_string())),
+            ("accent", self.accent.as_deref().map(quote)),
+            ("stripe", Some(self.stripe.to_string())),
+            ("status_emoji", Some(self.status_emoji.to_string())),
+            ("language", self.language.as_deref().map(quote)),
+            ("rtl", Some(self.rtl.to_string())),
+            ("theme", path(&self.theme)),
+            ("logo", path(&self.logo)),
+            ("recolour_logo", Some(self.recolour_logo.to_string())),
+            (
+                "layout",
+                Some(quote(match self.layout {
+                    Layout::Plain => "plain",
+                    Layout::Tree => "tree",
+                })),
+            ),
+            (
+                "hostname",
+                Some(quote(match self.hostname {
+                    HostnameLayout::Full => "full",
+                    HostnameLayout::Split => "split",
+                    HostnameLayout::Host => "host",
+                })),
+            ),
+            ("prefix", Some(quote(&self.prefix))),
+            ("footer", self.footer.as_deref().map(quote)),
+            ("qr", self.qr.as_deref().map(quote)),
+            ("animate", Some(self.animate.to_string())),
+            ("values_only", Some(self.values_only.to_string())),
+            ("badge", Some(self.badge.to_string())),
+            ("canonical", Some(self.canonical.to_string())),
+            ("json", Some(self.json.to_string())),
+            ("svg", path(&self.svg)),
+            ("png", path(&self.png)),
+            ("anon", Some(self.anon.to_string())),
+            (
+                "critical",
+                (!self.critical.is_empty()).then(|| {
+                    format!(
+                        "[{}]",
+                        self.critical
+                            .iter()
+                            .map(|name| quote(name))
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    )
+                }),
+            ),
+            ("timings", Some(self.timings.to_string())),
+            ("check_updates", Some(self.check_updates.to_string())),
+            ("plugins", Some(self.plugins.to_string())),
+            (
+                "watch",
+                self.watch
+                    .map(|interval| interval.as_secs_f64().to_string()),
+            ),
+            ("in_place", Some(self.in_place.to_string())),
+            ("uptime_since", path(&self.uptime_since)),
+            (
+                "uptime_period",
+                self.uptime_period.map(|period| {
+                    quote(match period {
+                        UptimePeriod::Month => "month",
+                        UptimePeriod::Install => "install",
+                    })
+                }),
+            ),
+            ("uptime_record", Some(self.uptime_record.to_string())),
+            (
+                "temperature_unit",
+                Some(quote(match self.temperature_unit {
+                    TemperatureUnit::Celsius => "celsius",
+                    TemperatureUnit::Fahrenheit => "fahrenheit",
+                })),
+            ),
+            ("os_base", Some(self.os_base.to_string())),
+            ("memory_verbose", Some(self.memory_verbose.to_string())),
+        ];
+        let filters = self
+            .filters
+            .iter()
+            .map(|(field, command)| format!("filter_{field} = {}\n", quote(command)));
+        options
+            .into_iter()
+            .filter_map(|(key, value)| Some(format!("{key} = {}\n", value?)))
+            .chain(filters)
+            .collect()
+    }
+}
+
+/* # themes */
+
+fn parse_rgb(hex: &str) -> Option<[u8; 3]> {
+    hex::decode(hex.trim_start_matches('#'))
+        .ok()?
+        .try_into()
+        .ok()
+}
+
+/// base16 slots in the order of the terminal colours they stand in for
+const BASE16_ANSI: [usize; 16] = [
+    0x0, 0x8, 0xb, 0xa, 0xd, 0xe, 0xc, 0x5, 0x3, 0x8, 0xb, 0xa, 0xd, 0xe, 0xc, 0x7,
+];
+
+/// the sixteen terminal colours, either straight from `colorN` Xresources
+/// or mapped from the `baseXX` entries of a base16 scheme
+fn read_theme(path: &std::path::Path) -> ConfigResult<Vec<[u8; 3]>> {
+    let mut base16 = [None; 16];
+    let mut ansi = [None; 16];
+
+    for line in std::fs::read_to_string(path)?.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (key, value) = (
+            key.trim(),
+            value.trim().trim_matches(|c| c == '"' || c == '\''),
+        );
+        if let Some(slot) = key
+            .strip_prefix("base")
+            .filter(|slot| slot.len() == 2)
+            .and_then(|slot| usize::from_str_radix(slot, 16).ok())
+            .filter(|&slot| slot < 16)
+        {
+            base16[slot] = parse_rgb(value);
+        } else if let Some(index) = key
+            .rsplit_once("color")
+            .and_then(|(_, index)| index.parse::<usize>().ok())
+            .filter(|&index| index < 16)
+        {
+            ansi[index] = parse_rgb(value);
+        }
+    }
+
+    if let Some(ansi) = ansi.into_iter().collect::<Option<Vec<[u8; 3]>>>() {
+        Ok(ansi)
+    } else if let Some(base16) = base16.into_iter().collect::<Option<Vec<[u8; 3]>>>() {
+        Ok(BASE16_ANSI.iter().map(|&slot| base16[slot]).collect())
+    } else {
+        simple_error::bail!("{} is missing some of the sixteen colours", path.display())
+    }
+}
+
+/* # command line */
+
+impl Config {
+    /// besides `--key value`, takes `--no-key` for switches and `--no-field`
+    /// to drop a single field from whichever list ends up selected
+    fn apply_args(&mut self) -> ConfigResult<()> {
+        let mut args = std::env::args().skip(1);
+        let mut dropped = Vec::new();
+
+        while let Some(arg) = args.next() {
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) => (flag.to_owned(), Some(value.to_owned())),
+                None => (arg, None),
+            };
+            let key = flag
+                .strip_prefix("--")
+                .ok_or_else(|| simple_error::simple_error!("unrecognised argument {}", flag))?
+                .replace('-', "_")
+                .replace("color", "colour");
+
+            if let Some(negated) = key.strip_prefix("no_") {
+                if SWITCHES.contains(&negated) {
+                    self.set(negated, "false")?;
+                } else if find_field(negated).is_some() {
+                    dropped.push(negated.to_owned());
+                } else {
+                    simple_error::bail!("unrecognised argument {}", flag);
+                }
+                continue;
+            }
+
+            let value = match inline {
+                Some(value) => value,
+                None if SWITCHES.contains(&key.as_str()) => "true".to_owned(),
+                None => args
+                    .next()
+                    .ok_or_else(|| simple_error::simple_error!("{} expects a value", flag))?,
+            };
+            self.set(&key, &value)?;
+        }
+
+        self.fields.retain(|name| !dropped.contains(name));
+        Ok(())
+    }
+}
@@ -1,17 +1,307 @@
 use core::fmt::Write;
 use systemstat::Platform;
 
+use crate::catalog::translate;
+
 /* # constants */
 
-const COLOUR: &str = "\x1b[36m";
-const RESET: &str = "\x1b[0m";
+pub const RESET: &str = "\x1b[0m";
+
+/// the terminal's own sixteen colours, used unless a theme says otherwise
+pub const PALETTE: [&str; 16] = [
+    "\x1b[30m", "\x1b[31m", "\x1b[32m", "\x1b[33m", "\x1b[34m", "\x1b[35m", "\x1b[36m", "\x1b[37m",
+    "\x1b[90m", "\x1b[91m", "\x1b[92m", "\x1b[93m", "\x1b[94m", "\x1b[95m", "\x1b[96m", "\x1b[97m",
+];
+
+/// palette entry for icons and the hostname, cyan
+pub const ACCENT: usize = 6;
 
 type StringResult = Result<String, Box<dyn std::error::Error>>;
+pub type DatumResult = Result<Datum, Box<dyn std::error::Error>>;
 
 /* # pretty formatting */
 
-fn format_data(key: &str, value: &str) -> String {
-    format!(" {COLOUR}{key}{RESET} {value}")
+/// whether escapes and nerd font glyphs make it into the output
+#[derive(Clone)]
+pub struct Style {
+    pub colour: bool,
+    pub icons: bool,
+    pub emoji: bool,
+    /// leads every field line, a single space unless configured
+    pub prefix: String,
+    pub accent: String,
+    /// set the icons on a stripe of the accent instead of colouring them
+    pub stripe: bool,
+    /// values before icons, for right-to-left scripts
+    pub rtl: bool,
+    /// lines of art to print the fetch beside
+    pub logo: Vec<String>,
+    /// follow readings with a green, yellow or red circle
+    pub status_emoji: bool,
+    /// language code the fetch's own words are translated into
+    pub language: String,
+    pub palette: Vec<String>,
+}
+
+pub fn rgb_escape([red, green, blue]: [u8; 3]) -> String {
+    format!("\x1b[38;2;{red};{green};{blue}m")
+}
+
+/// the background counterpart of a foreground escape
+fn background_escape(foreground: &str) -> String {
+    let codes = foreground
+        .strip_prefix("\x1b[")
+        .and_then(|codes| codes.strip_suffix('m'))
+        .unwrap_or_default();
+    let codes = match codes.split_once(';') {
+        Some(("38", rest)) => format!("48;{rest}"),
+        _ => match codes.parse::<u8>() {
+            Ok(code @ (30..=37 | 90..=97)) => (code + 10).to_string(),
+            _ => codes.to_owned(),
+        },
+    };
+    format!("\x1b[{codes}m")
+}
+
+/// a single line of the fetch, before any styling is applied
+#[derive(Clone)]
+pub struct Datum {
+    pub icon: &'static str,
+    /// what the value is where the icon alone does not say, shown before it;
+    /// a plugin's own label is owned and left untranslated
+    pub label: Option<std::borrow::Cow<'static, str>>,
+    pub value: String,
+    /// words after the value, like what a count is of
+    pub suffix: Option<&'static str>,
+    /// english words within the value that are translated wherever they stand alone
+    pub words: Vec<&'static str>,
+    /// how full a bar drawn after the value is, from 0 to 1
+    pub usage: Option<f64>,
+    /// how close the reading is to trouble, from 0 to 1, where there is no bar to say so
+    pub status: Option<f64>,
+}
+
+impl Datum {
+    fn new(icon: &'static str, value: &str) -> Self {
+        Self {
+            icon,
+            label: None,
+            value: value.to_owned(),
+            suffix: None,
+            words: Vec::new(),
+            usage: None,
+            status: None,
+        }
+    }
+
+    fn with_usage(icon: &'static str, value: &str, usage: f64) -> Self {
+        Self {
+            usage: Some(usage.clamp(0.0, 1.0)),
+            ..Self::new(icon, value)
+        }
+    }
+
+    /// `count` of `noun`, or of `nouns` when there is not exactly one
+    fn counted(icon: &'static str, count: usize, noun: &'static str, nouns: &'static str) -> Self {
+        Self {
+            suffix: Some(plural(count, noun, nouns)),
+            ..Self::new(icon, &count.to_string())
+        }
+    }
+
+    fn with_status(self, status: f64) -> Self {
+        Self {
+            status: Some(status.clamp(0.0, 1.0)),
+            ..self
+        }
+    }
+
+    fn with_suffix(self, suffix: &'static str) -> Self {
+        Self {
+            suffix: Some(suffix),
+            ..self
+        }
+    }
+
+    fn with_words(mut self, words: &[&'static str]) -> Self {
+        self.words.extend(words);
+        self
+    }
+
+    fn with_label(self, label: &'static str) -> Self {
+        Self {
+            label: Some(std::borrow::Cow::Borrowed(label)),
+            ..self
+        }
+    }
+
+    /// the value between its label and suffix, as the fetch shows it in `language`
+    pub fn text(&self, language: &str) -> String {
+        let label = match &self.label {
+            Some(std::borrow::Cow::Borrowed(label)) => translate(language, label),
+            Some(std::borrow::Cow::Owned(label)) => label,
+            None => return self.bare(language),
+        };
+        format!("{label}: {}", self.bare(language))
+    }
+
+    /// the value and its suffix, without the label a field's name already gives
+    pub fn bare(&self, language: &str) -> String {
+        let value = self.words.iter().fold(self.value.clone(), |value, word| {
+            translate_word(&value, word, language)
+        });
+        match self.suffix {
+            Some(suffix) => format!("{value} {}", translate(language, suffix)),
+            None => value,
+        }
+    }
+}
+
+/// `text` with `word` put into `language` wherever it is not part of a longer word
+fn translate_word(text: &str, word: &'static str, language: &str) -> String {
+    let translated = translate(language, word);
+    let mut result = String::new();
+    let mut last = 0;
+    for (index, _) in text.match_indices(word) {
+        let before = text[..index].chars().next_back();
+        let after = text[index + word.len()..].chars().next();
+        if !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric) {
+            result.push_str(&text[last..index]);
+            result.push_str(translated);
+            last = index + word.len();
+        }
+    }
+    result.push_str(&text[last..]);
+    result
+}
+
+/// emoji standing in for nerd font glyphs, with the variation selector
+/// so terminals pick the colour presentation
+fn emoji_icon(icon: &str) -> Option<&'static str> {
+    match icon {
+        "\u{e711}" => Some("\u{1f34e}\u{fe0f}"),
+        "\u{e712}" => Some("\u{1f427}\u{fe0f}"),
+        "\u{f489}" => Some("\u{1f41a}\u{fe0f}"),
+        "\u{f64f}" => Some("\u{23f1}\u{fe0f}"),
+        "\u{f009}" => Some("\u{1fa9f}\u{fe0f}"),
+        "\u{f013}" => Some("\u{2699}\u{fe0f}"),
+        "\u{f0a0}" => Some("\u{1f4bd}\u{fe0f}"),
+        "\u{f0c0}" => Some("\u{1f465}\u{fe0f}"),
+        "\u{f108}" => Some("\u{1f5a5}\u{fe0f}"),
+        "\u{f023}" => Some("\u{1f512}\u{fe0f}"),
+        "\u{f1eb}" => Some("\u{1f4f6}\u{fe0f}"),
+        "\u{f10b}" => Some("\u{1f4f1}\u{fe0f}"),
+        "\u{f6ff}" => Some("\u{1f50c}\u{fe0f}"),
+        "\u{f132}" => Some("\u{1f6e1}\u{fe0f}"),
+        "\u{f0f3}" => Some("\u{1f514}\u{fe0f}"),
+        "\u{f0ea}" => Some("\u{1f4cb}\u{fe0f}"),
+        "\u{f0ac}" => Some("\u{1f310}\u{fe0f}"),
+        "\u{f2c9}" => Some("\u{1f321}\u{fe0f}"),
+        "\u{f240}" => Some("\u{1f50b}\u{fe0f}"),
+        _ => None,
+    }
+}
+
+/// green, yellow or red circle for how near trouble a reading is
+fn status_emoji(status: f64) -> &'static str {
+    match status {
+        status if status < 0.5 => "\u{1f7e2}",
+        status if status < 0.8 => "\u{1f7e1}",
+        _ => "\u{1f534}",
+    }
+}
+
+/// ten cells filled in proportion to `usage`, green then yellow then red as it fills up
+fn format_bar(usage: f64, style: &Style) -> String {
+    const WIDTH: usize = 10;
+    let filled = (usage * WIDTH as f64).round() as usize;
+    let bar = format!(
+        "{}{}",
+        "\u{2588}".repeat(filled),
+        "\u{2591}".repeat(WIDTH - filled)
+    );
+    if !style.colour {
+        return bar;
+    }
+    let colour = match usage {
+        usage if usage < 0.5 => &style.palette[2],
+        usage if usage < 0.8 => &style.palette[3],
+        _ => &style.palette[1],
+    };
+    format!("{colour}{bar}{RESET}")
+}
+
+pub fn format_data(datum: &Datum, style: &Style) -> String {
+    let Style { prefix, accent, .. } = style;
+    let icon = if style.emoji {
+        emoji_icon(datum.icon).unwrap_or(datum.icon)
+    } else {
+        datum.icon
+    };
+    let mut value = match datum.usage {
+        Some(usage) => format!(
+            "{} {}",
+            datum.text(&style.language),
+            format_bar(usage, style)
+        ),
+        None => datum.text(&style.language),
+    };
+    if let Some(status) = datum.status.or(datum.usage).filter(|_| style.status_emoji) {
+        value = format!("{value} {}", status_emoji(status));
+    }
+    let icon = match (style.icons, style.colour) {
+        (true, true) if style.stripe => format!(
+            "{}{} {icon} {RESET}",
+            background_escape(accent),
+            style.palette[0]
+        ),
+        (true, true) => format!("{accent}{icon}{RESET}"),
+        (true, false) => icon.to_owned(),
+        (false, _) => String::new(),
+    };
+    match (icon.is_empty(), style.rtl) {
+        (true, false) => format!("{prefix}{value}"),
+        (false, false) => format!("{prefix}{icon} {value}"),
+        (true, true) => format!("{value}{}", prefix.chars().rev().collect::<String>()),
+        (false, true) => format!("{value} {icon}{}", prefix.chars().rev().collect::<String>()),
+    }
+}
+
+fn truncate(value: &str, width: usize) -> String {
+    if value.chars().count() > width {
+        format!(
+            "{}\u{2026}",
+            value.chars().take(width - 1).collect::<String>()
+        )
+    } else {
+        value.to_owned()
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    let mut size = bytes as f64;
+    let mut units = ["B", "KiB", "MiB", "GiB", "TiB", "PiB"]
+        .into_iter()
+        .peekable();
+    let mut unit = units.next().unwrap_or_default();
+    while size >= 1024.0 && units.peek().is_some() {
+        size /= 1024.0;
+        unit = units.next().unwrap_or_default();
+    }
+    if size < 10.0 && unit != "B" {
+        format!("{size:.1} {unit}")
+    } else {
+        format!("{size:.0} {unit}")
+    }
+}
+
+/// `noun` for exactly one, `nouns` for any other count
+fn plural(count: usize, noun: &'static str, nouns: &'static str) -> &'static str {
+    if count == 1 {
+        noun
+    } else {
+        nouns
+    }
 }
 
 fn format_uptime(time: core::time::Duration) -> StringResult {
@@ -22,7 +312,7 @@ fn format_uptime(time: core::time::Duration) -> StringResult {
     if uptime_days > 0 {
         write!(display, "{uptime_days}d ")?;
     }
-    let uptime_hours = (uptime_seconds % 24 * 60 * 60) / (60 * 60);
+    let uptime_hours = (uptime_seconds % (24 * 60 * 60)) / (60 * 60);
     if uptime_hours > 0 {
         write!(display, "{uptime_hours}h ")?;
     }
@@ -31,29 +321,170 @@ fn format_uptime(time: core::time::Duration) -> StringResult {
         write!(display, "{uptime_minutes}m")?;
     }
 
-    Ok(format_data("\u{f64f}", &display))
+    Ok(display)
+}
+
+/* # reading external sources */
+
+fn read_command(program: &str, args: &[&str]) -> StringResult {
+    let output = std::process::Command::new(program).args(args).output()?;
+    if !output.status.success() {
+        simple_error::bail!("{} exited unsuccessfully", program);
+    }
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+fn count_lines(output: &str) -> usize {
+    output
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .count()
+}
+
+/// values of every occurrence of `key` in a json document, without any real parsing;
+/// good enough for the flat numbers and booleans window managers report
+fn json_values<'a>(json: &'a str, key: &str) -> Vec<&'a str> {
+    json.split(&format!("\"{key}\":"))
+        .skip(1)
+        .filter_map(|rest| {
+            rest.trim_start()
+                .split(|c: char| c == ',' || c == '}' || c == ']' || c.is_whitespace())
+                .next()
+        })
+        .collect()
+}
+
+fn ensure_linux() -> Result<(), Box<dyn std::error::Error>> {
+    match nix::sys::utsname::uname().sysname() {
+        "Linux" => Ok(()),
+        _ => simple_error::bail!("only available on linux"),
+    }
+}
+
+/// the language code the locale starts with, like `de` for `de_DE.UTF-8`
+pub fn locale_language() -> String {
+    let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|locale| !locale.is_empty())
+        .unwrap_or_default();
+    locale
+        .split(['_', '.', '@'])
+        .next()
+        .unwrap_or_default()
+        .to_owned()
+}
+
+/// whether the locale's language is written right to left
+pub fn is_rtl_locale() -> bool {
+    ["ar", "he", "fa", "ur", "yi", "ps", "dv", "sd", "ug", "ckb"]
+        .contains(&locale_language().as_str())
+}
+
+/// anything running off a battery counts as a laptop
+pub fn is_laptop() -> bool {
+    systemstat::System::new().battery_life().is_ok()
+}
+
+/// nothing to draw on: no graphical session and no connected display
+pub fn is_headless() -> bool {
+    std::env::var_os("DISPLAY").is_none()
+        && std::env::var_os("WAYLAND_DISPLAY").is_none()
+        && get_displays_count().is_err()
+}
+
+fn cache_dir() -> Result<std::path::PathBuf, Box<dyn std::error::Error>> {
+    Ok(match std::env::var_os("XDG_CACHE_HOME") {
+        Some(cache) => std::path::PathBuf::from(cache),
+        None => std::path::PathBuf::from(std::env::var("HOME")?).join(".cache"),
+    }
+    .join("orhfetch"))
 }
 
 /* # retrieving information */
 
 /* ## hostname */
 
-pub fn get_hostname() -> StringResult {
-    Ok(format!(
-        "{COLOUR}{user}{RESET}@{COLOUR}{host}{RESET}",
-        user = std::env::var("USER")?,
-        host = match std::env::var("HOSTNAME") {
-            Ok(name) => name,
-            Err(_) =>
-                match core::str::from_utf8(&std::process::Command::new("hostname").output()?.stdout)
-                {
-                    Ok(name) => name.to_owned().replace('\n', ""),
-                    Err(_) => nix::sys::utsname::uname().nodename().to_owned(),
-                },
-        },
+/// how much of `user@host` to show and on how many lines
+#[derive(Clone, Copy)]
+pub enum HostnameLayout {
+    Full,
+    /// `user` over `@host`, for narrow spaces
+    Split,
+    Host,
+}
+
+fn read_host() -> StringResult {
+    Ok(match std::env::var("HOSTNAME") {
+        Ok(name) => name,
+        Err(_) => {
+            match core::str::from_utf8(&std::process::Command::new("hostname").output()?.stdout) {
+                Ok(name) => name.to_owned().replace('\n', ""),
+                Err(_) => nix::sys::utsname::uname().nodename().to_owned(),
+            }
+        }
+    })
+}
+
+/// `user@host`, or literally that when `anonymous`
+pub fn get_hostname(anonymous: bool) -> DatumResult {
+    if anonymous {
+        return Ok(Datum::new("", "user@host"));
+    }
+    Ok(Datum::new(
+        "",
+        &format!("{}@{}", std::env::var("USER")?, read_host()?),
     ))
 }
 
+/// a bright colour picked by hashing the host name, the same on every run
+/// but most likely different from the next machine's
+pub fn hostname_rgb() -> Result<[u8; 3], Box<dyn std::error::Error>> {
+    // fnv-1a
+    let hash = read_host()?
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    let (saturation, value) = (0.6, 0.95);
+    let hue = (hash % 360) as f64 / 60.0;
+    let chroma = value * saturation;
+    let second = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+    let (red, green, blue) = match hue as u8 {
+        0 => (chroma, second, 0.0),
+        1 => (second, chroma, 0.0),
+        2 => (0.0, chroma, second),
+        3 => (0.0, second, chroma),
+        4 => (second, 0.0, chroma),
+        _ => (chroma, 0.0, second),
+    };
+    let channel = |level: f64| ((level + value - chroma) * 255.0).round() as u8;
+    Ok([channel(red), channel(green), channel(blue)])
+}
+
+/// the hostname field's own styling, accented user and host in place of an icon
+pub fn format_hostname(datum: &Datum, style: &Style, layout: HostnameLayout) -> Vec<String> {
+    let (colour, reset) = if style.colour {
+        (style.accent.as_str(), RESET)
+    } else {
+        ("", "")
+    };
+    let (user, host) = datum.value.split_once('@').unwrap_or(("", &datum.value));
+    let (user, host) = (
+        format!("{colour}{user}{reset}"),
+        format!("{colour}{host}{reset}"),
+    );
+    let lines = match layout {
+        HostnameLayout::Full => vec![format!("{user}@{host}")],
+        HostnameLayout::Split => vec![user, format!("@{host}")],
+        HostnameLayout::Host => vec![host],
+    };
+    lines
+        .into_iter()
+        .map(|line| format!("{}{line}", style.prefix))
+        .collect()
+}
+
 /* ## operating system */
 
 fn read_mac_release() -> StringResult {
@@ -90,34 +521,58 @@ fn read_lsb_release() -> StringResult {
             .output()?
             .stdout,
     )?
+    .trim()
     .to_owned())
 }
 
-fn read_os_release() -> StringResult {
-    Ok(std::fs::read_to_string("/etc/os_release")?
-        .split('\n')
-        .find(|s| s.starts_with("PRETTY_NAME"))
-        .ok_or_else(|| simple_error::simple_error!("unrecognised linux distro"))?
-        .strip_prefix("PRETTY_NAME=")
-        .ok_or_else(|| simple_error::simple_error!("unrecognised linux distro"))?
+/// an unquoted value out of `/etc/os-release`
+fn read_os_release_key(key: &str) -> StringResult {
+    Ok(std::fs::read_to_string("/etc/os-release")?
+        .lines()
+        .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+        .ok_or_else(|| simple_error::simple_error!("no {} in os-release", key))?
         .replace('"', ""))
 }
 
-pub fn get_os() -> StringResult {
+fn read_os_release() -> StringResult {
+    read_os_release_key("PRETTY_NAME")
+}
+
+/// the distro a derivative is built on, like `Ubuntu` for pop!_os
+fn read_base_distro() -> StringResult {
+    let like = read_os_release_key("ID_LIKE")?;
+    let base = like
+        .split_whitespace()
+        .next()
+        .ok_or_else(|| simple_error::simple_error!("no base distro"))?;
+    let mut letters = base.chars();
+    Ok(letters
+        .next()
+        .map(|first| first.to_uppercase().chain(letters).collect())
+        .unwrap_or_default())
+}
+
+pub fn get_os(base: bool) -> DatumResult {
     match nix::sys::utsname::uname().sysname() {
-        "Darwin" => Ok(format_data("\u{e711}", &read_mac_release()?)),
-        "Linux" => Ok(format_data(
-            "\u{e712}",
-            &read_lsb_release().or_else(|_| read_os_release())?,
-        )),
+        "Darwin" => Ok(Datum::new("\u{e711}", &read_mac_release()?)),
+        "Linux" => {
+            let name = read_lsb_release().or_else(|_| read_os_release())?;
+            Ok(Datum::new(
+                "\u{e712}",
+                &match read_base_distro() {
+                    Ok(distro) if base => format!("{name} ({distro}-based)"),
+                    _ => name,
+                },
+            ))
+        }
         _ => simple_error::bail!("unrecognised os"),
     }
 }
 
 /* ## shell */
 
-pub fn get_shell() -> StringResult {
-    Ok(format_data(
+pub fn get_shell() -> DatumResult {
+    Ok(Datum::new(
         "\u{f489}",
         std::env::var("SHELL")?
             .strip_prefix("/bin/")
@@ -125,26 +580,1885 @@ pub fn get_shell() -> StringResult {
     ))
 }
 
+/* ## shell framework */
+
+/// frameworks and prompts the shell has loaded, going by the variables they export,
+/// or for prezto, which exports none, by whether `.zshrc` sources it
+pub fn get_shell_framework() -> DatumResult {
+    let set = |name: &str| std::env::var_os(name).is_some_and(|value| !value.is_empty());
+    let zdotdir = match std::env::var("ZDOTDIR") {
+        Ok(zdotdir) => zdotdir,
+        Err(_) => std::env::var("HOME")?,
+    };
+    let frameworks: Vec<&str> = [
+        ("oh-my-zsh", set("ZSH")),
+        (
+            "prezto",
+            std::fs::read_to_string(std::path::Path::new(&zdotdir).join(".zshrc"))
+                .is_ok_and(|zshrc| zshrc.contains("zprezto/init.zsh")),
+        ),
+        ("oh-my-bash", set("OSH")),
+        ("oh-my-fish", set("OMF_PATH")),
+        ("starship", set("STARSHIP_SHELL")),
+    ]
+    .iter()
+    .filter(|(_, loaded)| *loaded)
+    .map(|&(framework, _)| framework)
+    .collect();
+    if frameworks.is_empty() {
+        simple_error::bail!("no shell framework");
+    }
+    Ok(Datum::new("\u{f489}", &frameworks.join(" + ")).with_label("framework"))
+}
+
+/* ## shell startup */
+
+/// how `child` exited, or nothing once `limit` passes and it has been killed
+fn wait_within(
+    child: &mut std::process::Child,
+    limit: core::time::Duration,
+) -> std::io::Result<Option<std::process::ExitStatus>> {
+    let start = std::time::Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if start.elapsed() > limit {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(None);
+        }
+        std::thread::sleep(core::time::Duration::from_millis(1));
+    }
+}
+
+/// how long an interactive `$SHELL` takes to read its rc files and exit,
+/// given up on after a few seconds in case something in them waits for input
+pub fn get_shell_startup() -> DatumResult {
+    const LIMIT: core::time::Duration = core::time::Duration::from_secs(5);
+
+    let shell = std::env::var("SHELL")?;
+    let start = std::time::Instant::now();
+    let mut child = std::process::Command::new(&shell)
+        .args(["-i", "-c", "exit"])
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()?;
+    let Some(status) = wait_within(&mut child, LIMIT)? else {
+        simple_error::bail!("{} took longer than {}s to start", shell, LIMIT.as_secs());
+    };
+    let elapsed = start.elapsed();
+    if !status.success() {
+        simple_error::bail!("{} exited unsuccessfully", shell);
+    }
+    Ok(Datum::new("\u{f489}", &format!("{}ms", elapsed.as_millis())).with_label("shell startup"))
+}
+
+/* ## dotfiles */
+
+/// every file chezmoi or yadm manages, or that sits in a stow directory under git
+fn read_dotfiles() -> StringResult {
+    if let Ok(files) = read_command("chezmoi", &["managed", "--include=files"])
+        .or_else(|_| read_command("yadm", &["list", "-a"]))
+    {
+        return Ok(files);
+    }
+    let home = std::path::PathBuf::from(std::env::var("HOME")?);
+    let stow = ["dotfiles", ".dotfiles"]
+        .iter()
+        .map(|name| home.join(name))
+        .find(|path| path.is_dir())
+        .ok_or_else(|| simple_error::simple_error!("no dotfiles manager"))?;
+    read_command("git", &["-C", &stow.to_string_lossy(), "ls-files"])
+}
+
+pub fn get_dotfiles() -> DatumResult {
+    let tracked = count_lines(&read_dotfiles()?);
+    Ok(Datum::new("\u{f013}", &tracked.to_string())
+        .with_label("dotfiles")
+        .with_suffix("tracked"))
+}
+
+/* ## boot mode */
+
+pub fn get_boot_mode() -> DatumResult {
+    ensure_linux()?;
+    Ok(Datum::new(
+        "\u{f2db}",
+        if std::path::Path::new("/sys/firmware/efi").exists() {
+            "UEFI"
+        } else {
+            "BIOS"
+        },
+    ))
+}
+
+/* ## processor and graphics */
+
+/// glyph for a chip, its maker's logo if asked for and nerd fonts has one
+fn chip_icon(name: &str, vendor_icons: bool) -> &'static str {
+    // nerd fonts carries no intel, amd or nvidia logo, font awesome never having
+    // had them and material design having dropped its brand icons
+    const LOGOS: &[(&str, &str)] = &[("apple", "\u{f179}")];
+    let name = name.to_lowercase();
+    LOGOS
+        .iter()
+        .find(|(vendor, _)| vendor_icons && name.contains(vendor))
+        .map_or("\u{f2db}", |(_, logo)| logo)
+}
+
+pub fn get_cpu(vendor_icons: bool) -> DatumResult {
+    let name = match nix::sys::utsname::uname().sysname() {
+        "Darwin" => read_command("sysctl", &["-n", "machdep.cpu.brand_string"])?
+            .trim()
+            .to_owned(),
+        _ => std::fs::read_to_string("/proc/cpuinfo")?
+            .lines()
+            .find_map(|line| line.strip_prefix("model name"))
+            .and_then(|line| line.split_once(':'))
+            .ok_or_else(|| simple_error::simple_error!("no cpu model"))?
+            .1
+            .trim()
+            .to_owned(),
+    };
+    Ok(Datum::new(chip_icon(&name, vendor_icons), &name))
+}
+
+#[cfg(feature = "gpu")]
+pub fn get_gpu(vendor_icons: bool) -> DatumResult {
+    let glxinfo = read_command("glxinfo", &["-B"])?;
+    let name = glxinfo
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("OpenGL renderer string:"))
+        .ok_or_else(|| simple_error::simple_error!("no opengl renderer"))?
+        .trim();
+    Ok(Datum::new(chip_icon(name, vendor_icons), name))
+}
+
+/* ## userland */
+
+/// word size of the installed programs, which a 64-bit kernel may well run 32-bit
+pub fn get_userland() -> DatumResult {
+    use std::io::Read;
+
+    ensure_linux()?;
+
+    // byte four of an elf header is its class, 1 for 32-bit and 2 for 64-bit
+    let mut header = [0; 5];
+    let class = std::fs::File::open("/bin/sh")
+        .and_then(|mut sh| sh.read_exact(&mut header))
+        .ok()
+        .filter(|()| header.starts_with(b"\x7fELF"))
+        .map(|()| header[4]);
+    let bits = match class {
+        Some(1) => "32".to_owned(),
+        Some(2) => "64".to_owned(),
+        _ => read_command("getconf", &["LONG_BIT"])?.trim().to_owned(),
+    };
+    Ok(Datum::new("\u{f013}", &format!("{bits}-bit")).with_label("userland"))
+}
+
+/* ## libc */
+
+/// the loader an elf executable asks for in its PT_INTERP header, which is
+/// the libc the system itself runs on rather than any other merely installed
+fn read_interpreter(path: &str) -> Option<std::path::PathBuf> {
+    const PT_INTERP: usize = 3;
+
+    let elf = std::fs::read(path).ok()?;
+    if !elf.starts_with(b"\x7fELF") {
+        return None;
+    }
+    // byte four is the class as in get_userland, byte five 2 for big endian
+    let wide = *elf.get(4)? == 2;
+    let big = *elf.get(5)? == 2;
+    let read = |offset: usize, size: usize| -> Option<usize> {
+        let bytes = elf.get(offset..offset.checked_add(size)?)?;
+        let fold = |value: u64, &byte: &u8| (value << 8) | u64::from(byte);
+        let value = if big {
+            bytes.iter().fold(0, fold)
+        } else {
+            bytes.iter().rev().fold(0, fold)
+        };
+        usize::try_from(value).ok()
+    };
+    let (table, entry_size, entries) = if wide {
+        (read(0x20, 8)?, read(0x36, 2)?, read(0x38, 2)?)
+    } else {
+        (read(0x1c, 4)?, read(0x2a, 2)?, read(0x2c, 2)?)
+    };
+    (0..entries).find_map(|index| {
+        let header = table.checked_add(index.checked_mul(entry_size)?)?;
+        if read(header, 4)? != PT_INTERP {
+            return None;
+        }
+        let (offset, size) = if wide {
+            (read(header + 8, 8)?, read(header + 32, 8)?)
+        } else {
+            (read(header + 4, 4)?, read(header + 16, 4)?)
+        };
+        let name = elf.get(offset..offset.checked_add(size)?)?;
+        let name = name.strip_suffix(b"\0").unwrap_or(name);
+        Some(std::path::PathBuf::from(std::str::from_utf8(name).ok()?))
+    })
+}
+
+fn is_musl_loader(path: &std::path::Path) -> bool {
+    path.file_name()
+        .and_then(std::ffi::OsStr::to_str)
+        .is_some_and(|name| name.starts_with("ld-musl-"))
+}
+
+fn read_musl_version(loader: &std::path::Path) -> String {
+    // run bare, the loader introduces itself on stderr and exits unsuccessfully
+    std::process::Command::new(loader)
+        .output()
+        .map(|output| {
+            String::from_utf8_lossy(&output.stderr)
+                .lines()
+                .find_map(|line| line.strip_prefix("Version "))
+                .unwrap_or_default()
+                .trim()
+                .to_owned()
+        })
+        .unwrap_or_default()
+}
+
+fn read_glibc_version() -> Option<String> {
+    let ldd = read_command("ldd", &["--version"]).ok()?;
+    let banner = ldd.lines().next().unwrap_or_default();
+    (banner.contains("GLIBC") || banner.contains("GNU libc"))
+        .then(|| banner.rsplit(' ').next().unwrap_or_default().to_owned())
+}
+
+/// the libc `/bin/sh` is linked against, or failing that glibc if ldd is its,
+/// and only then a musl loader that may just be there for cross builds
+pub fn get_libc() -> DatumResult {
+    ensure_linux()?;
+    let interpreter = read_interpreter("/bin/sh");
+    let libc = match interpreter.filter(|loader| is_musl_loader(loader)) {
+        Some(loader) => format!("musl {}", read_musl_version(&loader)),
+        None => match read_glibc_version() {
+            Some(version) => format!("glibc {version}"),
+            None => {
+                let loader = std::fs::read_dir("/lib")?
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .find(|path| is_musl_loader(path))
+                    .ok_or_else(|| simple_error::simple_error!("unrecognised libc"))?;
+                format!("musl {}", read_musl_version(&loader))
+            }
+        },
+    };
+    Ok(Datum::new("\u{f121}", libc.trim()).with_label("libc"))
+}
+
+/* ## graphics driver */
+
+#[cfg(feature = "gpu")]
+pub fn get_mesa() -> DatumResult {
+    let glxinfo = read_command("glxinfo", &["-B"])?;
+    let version = glxinfo
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("OpenGL version string:"))
+        .ok_or_else(|| simple_error::simple_error!("no opengl version"))?
+        .trim();
+    // "4.6 (Compatibility Profile) Mesa 24.0.3" is only interesting from the driver on
+    Ok(Datum::new(
+        "\u{f1b2}",
+        &match version.find("Mesa") {
+            Some(index) => version[index..]
+                .split_whitespace()
+                .take(2)
+                .collect::<Vec<&str>>()
+                .join(" "),
+            None => version.to_owned(),
+        },
+    ))
+}
+
+/* ## display scaling */
+
+pub fn get_scale() -> DatumResult {
+    let scale: f64 = match std::env::var("GDK_SCALE").or_else(|_| std::env::var("QT_SCALE_FACTOR"))
+    {
+        Ok(scale) => scale.trim().parse()?,
+        Err(_) => read_command(
+            "gsettings",
+            &["get", "org.gnome.desktop.interface", "text-scaling-factor"],
+        )?
+        .trim()
+        .parse()?,
+    };
+    Ok(Datum::new("\u{f108}", &format!("{scale}x")).with_label("scale"))
+}
+
+/* ## preemption */
+
+/// the model picked at boot when debugfs offers it, like `none (voluntary) full`,
+/// otherwise what uname's build string says the kernel was built with
+pub fn get_preempt() -> DatumResult {
+    ensure_linux()?;
+    let model = match std::fs::read_to_string("/sys/kernel/debug/sched/preempt") {
+        Ok(models) => models
+            .split_whitespace()
+            .find_map(|model| model.strip_prefix('(')?.strip_suffix(')'))
+            .ok_or_else(|| simple_error::simple_error!("no preemption model selected"))?
+            .to_owned(),
+        Err(_) => {
+            let uname = nix::sys::utsname::uname();
+            let build = uname.version();
+            if build.contains("PREEMPT_RT") {
+                "rt".to_owned()
+            } else if build.contains("PREEMPT_DYNAMIC") {
+                "dynamic".to_owned()
+            } else if build.contains("PREEMPT") {
+                "full".to_owned()
+            } else {
+                simple_error::bail!("unrecognised preemption model");
+            }
+        }
+    };
+    Ok(Datum::new("\u{f013}", &model).with_label("preempt"))
+}
+
+/* ## installed kernels */
+
+/// module trees there are, or the images in `/boot` where there are none,
+/// as containers and some distributions ship an empty `/lib/modules`
+pub fn get_kernels() -> DatumResult {
+    ensure_linux()?;
+    let count = |dir: &str, installed: fn(&std::fs::DirEntry) -> bool| {
+        std::fs::read_dir(dir).map_or(0, |entries| entries.flatten().filter(installed).count())
+    };
+    let kernels = match count("/lib/modules", |entry| entry.path().is_dir()) {
+        0 => count("/boot", |entry| {
+            entry.file_name().to_string_lossy().starts_with("vmlinuz")
+        }),
+        kernels => kernels,
+    };
+    if kernels == 0 {
+        simple_error::bail!("no installed kernels");
+    }
+    Ok(Datum::counted("\u{f1b3}", kernels, "kernel", "kernels"))
+}
+
+/* ## threads */
+
+pub fn get_threads() -> DatumResult {
+    // the fourth field of loadavg is "runnable/total" scheduling entities, i.e. threads
+    let threads: usize = std::fs::read_to_string("/proc/loadavg")?
+        .split_whitespace()
+        .nth(3)
+        .and_then(|entities| entities.split_once('/'))
+        .ok_or_else(|| simple_error::simple_error!("unrecognised loadavg"))?
+        .1
+        .parse()?;
+    Ok(Datum::counted("\u{f0ae}", threads, "thread", "threads"))
+}
+
+/* ## network connection */
+
+/// interface and little-endian hex gateway of the lowest-metric default route
+#[cfg(feature = "network")]
+fn read_default_route() -> Result<(String, String), Box<dyn std::error::Error>> {
+    std::fs::read_to_string("/proc/net/route")?
+        .lines()
+        .skip(1)
+        .map(|line| line.split_whitespace().collect::<Vec<&str>>())
+        .filter(|columns| columns.len() > 6 && columns[1] == "00000000")
+        .min_by_key(|columns| columns[6].parse::<u32>().unwrap_or(u32::MAX))
+        .map(|columns| (columns[0].to_owned(), columns[2].to_owned()))
+        .ok_or_else(|| simple_error::simple_error!("no default route").into())
+}
+
+#[cfg(feature = "network")]
+pub fn get_connection_type() -> DatumResult {
+    let (interface, _) = read_default_route()?;
+    let sysfs = std::path::Path::new("/sys/class/net").join(&interface);
+    Ok(
+        if ["tun", "tap", "wg", "ppp", "vpn"]
+            .iter()
+            .any(|prefix| interface.starts_with(prefix))
+        {
+            Datum::new("\u{f023}", "VPN")
+        } else if sysfs.join("wireless").exists() || sysfs.join("phy80211").exists() {
+            Datum::new("\u{f1eb}", "Wireless")
+        } else if ["wwan", "ww", "rmnet"]
+            .iter()
+            .any(|prefix| interface.starts_with(prefix))
+        {
+            Datum::new("\u{f10b}", "Mobile")
+        } else if sysfs.exists() {
+            Datum::new("\u{f6ff}", "Wired")
+        } else {
+            simple_error::bail!("unrecognised interface {}", interface)
+        },
+    )
+}
+
+/* ## forwarding */
+
+#[cfg(feature = "network")]
+pub fn get_forwarding() -> DatumResult {
+    ensure_linux()?;
+    let read =
+        |path: &str| -> StringResult { Ok(std::fs::read_to_string(path)?.trim().to_owned()) };
+    let forward = match read("/proc/sys/net/ipv4/ip_forward")?.as_str() {
+        "0" => "off",
+        _ => "on",
+    };
+    let rp_filter = match read("/proc/sys/net/ipv4/conf/all/rp_filter")?.as_str() {
+        "0" => "off",
+        "1" => "strict",
+        _ => "loose",
+    };
+    Ok(
+        Datum::new("\u{f0ac}", &format!("{forward}, rp_filter {rp_filter}"))
+            .with_label("forwarding"),
+    )
+}
+
+/* ## top process */
+
+pub fn get_top_process() -> DatumResult {
+    let (usage, command) = read_command("ps", &["-Ao", "pcpu=,comm="])?
+        .lines()
+        .filter_map(|line| {
+            let (usage, command) = line.trim().split_once(char::is_whitespace)?;
+            Some((usage.parse::<f32>().ok()?, command.trim().to_owned()))
+        })
+        .max_by(|(left, _), (right, _)| left.total_cmp(right))
+        .ok_or_else(|| simple_error::simple_error!("no processes"))?;
+    // macOS reports the full path of the executable
+    let name = command.rsplit('/').next().unwrap_or(&command);
+    Ok(Datum::new("\u{f0e4}", &format!("{name} ({usage:.0}%)")))
+}
+
+/* ## firewall */
+
+#[cfg(feature = "network")]
+fn format_rules(tool: &str, rules: usize) -> String {
+    match rules {
+        0 => format!("{tool} (inactive)"),
+        _ => format!("{tool} ({rules} {})", plural(rules, "rule", "rules")),
+    }
+}
+
+#[cfg(feature = "network")]
+fn read_firewall() -> StringResult {
+    if let Ok(status) = read_command("ufw", &["status"]) {
+        return Ok(match status.lines().next() {
+            Some("Status: active") => "ufw (active)".to_owned(),
+            _ => "ufw (inactive)".to_owned(),
+        });
+    }
+    // firewall-cmd answers "not running" with a failing exit code
+    if let Ok(output) = std::process::Command::new("firewall-cmd")
+        .arg("--state")
+        .output()
+    {
+        return Ok(if output.status.success() {
+            "firewalld (active)".to_owned()
+        } else {
+            "firewalld (inactive)".to_owned()
+        });
+    }
+    if let Ok(ruleset) = read_command("nft", &["list", "ruleset"]) {
+        let rules = ruleset
+            .lines()
+            .map(str::trim)
+            .filter(|line| {
+                !line.is_empty()
+                    && !line.ends_with('{')
+                    && *line != "}"
+                    && !line.starts_with("type ")
+                    && !line.starts_with("policy ")
+            })
+            .count();
+        return Ok(format_rules("nftables", rules));
+    }
+    let rules = read_command("iptables", &["-S"])?
+        .lines()
+        .filter(|line| line.starts_with("-A"))
+        .count();
+    Ok(format_rules("iptables", rules))
+}
+
+#[cfg(feature = "network")]
+pub fn get_firewall() -> DatumResult {
+    Ok(Datum::new("\u{f132}", &read_firewall()?)
+        .with_words(&["active", "inactive", "rule", "rules"]))
+}
+
+/* ## displays */
+
+/// plug and play ids of the usual monitor makers, by the names they go by
+const MONITOR_VENDORS: &[(&str, &str)] = &[
+    ("ACR", "Acer"),
+    ("AOC", "AOC"),
+    ("APP", "Apple"),
+    ("AUO", "AUO"),
+    ("AUS", "ASUS"),
+    ("BNQ", "BenQ"),
+    ("BOE", "BOE"),
+    ("CMN", "Innolux"),
+    ("DEL", "Dell"),
+    ("GSM", "LG"),
+    ("HWP", "HP"),
+    ("LEN", "Lenovo"),
+    ("MSI", "MSI"),
+    ("PHL", "Philips"),
+    ("SAM", "Samsung"),
+    ("SDC", "Samsung"),
+    ("SHP", "Sharp"),
+    ("SNY", "Sony"),
+    ("VSC", "ViewSonic"),
+];
+
+/// maker and model from an edid blob, the model being the monitor name descriptor
+fn parse_edid(edid: &[u8]) -> Option<String> {
+    if edid.len() < 128 || edid[..8] != [0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00] {
+        return None;
+    }
+    // three letters of five bits each, 1 being `A`
+    let id = u16::from_be_bytes([edid[8], edid[9]]);
+    let id: String = [10, 5, 0]
+        .iter()
+        .map(|shift| char::from(b'@' + (id >> shift & 0x1f) as u8))
+        .collect();
+    let vendor = MONITOR_VENDORS
+        .iter()
+        .find(|(code, _)| *code == id)
+        .map_or(id.as_str(), |(_, name)| name);
+    let model = [54, 72, 90, 108]
+        .iter()
+        .map(|&offset| &edid[offset..offset + 18])
+        .find(|descriptor| descriptor[..3] == [0, 0, 0] && descriptor[3] == 0xfc)
+        .map(|descriptor| {
+            String::from_utf8_lossy(&descriptor[5..])
+                .split('\n')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_owned()
+        })?;
+    // names tend to lead with the maker already, often in capitals
+    let model = match model.split_once(' ') {
+        Some((maker, rest)) if maker.eq_ignore_ascii_case(vendor) => rest.to_owned(),
+        _ => model,
+    };
+    Some(format!("{vendor} {model}"))
+}
+
+/// connected outputs, named by model where their edid can be read
+pub fn get_displays_count() -> DatumResult {
+    let (connected, models) = match std::fs::read_dir("/sys/class/drm") {
+        Ok(outputs) => {
+            let outputs: Vec<std::path::PathBuf> = outputs
+                .flatten()
+                .map(|output| output.path())
+                .filter(|output| {
+                    std::fs::read_to_string(output.join("status"))
+                        .is_ok_and(|status| status.trim() == "connected")
+                })
+                .collect();
+            let models: Vec<String> = outputs
+                .iter()
+                .filter_map(|output| parse_edid(&std::fs::read(output.join("edid")).ok()?))
+                .collect();
+            (outputs.len(), models)
+        }
+        Err(_) => (
+            read_command("xrandr", &["--listmonitors"])?
+                .lines()
+                .next()
+                .and_then(|line| line.strip_prefix("Monitors:"))
+                .ok_or_else(|| simple_error::simple_error!("unrecognised xrandr output"))?
+                .trim()
+                .parse()?,
+            Vec::new(),
+        ),
+    };
+    if connected == 0 {
+        simple_error::bail!("no connected displays");
+    }
+    let noun = plural(connected, "display", "displays");
+    let count = format!("{connected} {noun}");
+    let value = if models.is_empty() {
+        count
+    } else {
+        format!("{count} ({})", models.join(", "))
+    };
+    Ok(Datum::new("\u{f26c}", &value).with_words(&[noun]))
+}
+
+/* ## memory */
+
+/// used, free and cached kibibytes the way `free` counts them
+fn read_memory_breakdown() -> Result<(u64, u64, u64), Box<dyn std::error::Error>> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo")?;
+    let read = |key: &str| {
+        read_meminfo(&meminfo, key)
+            .ok_or_else(|| simple_error::simple_error!("no {} in meminfo", key))
+    };
+    let (total, free) = (read("MemTotal")?, read("MemFree")?);
+    let cache = read("Buffers")? + read("Cached")? + read("SReclaimable").unwrap_or(0);
+    Ok((total.saturating_sub(free + cache), free, cache))
+}
+
+pub fn get_memory(verbose: bool) -> DatumResult {
+    if verbose {
+        if let Ok((used, free, cache)) = read_memory_breakdown() {
+            let gib = |kib: u64| kib as f64 / (1024.0 * 1024.0);
+            return Ok(Datum::new(
+                "\u{f85a}",
+                &format!(
+                    "used {:.1} / free {:.1} / cache {:.1} GiB",
+                    gib(used),
+                    gib(free),
+                    gib(cache)
+                ),
+            ));
+        }
+    }
+    let memory = systemstat::System::new().memory()?;
+    let (total, free) = (memory.total.as_u64(), memory.free.as_u64());
+    Ok(Datum::with_usage(
+        "\u{f85a}",
+        &format!("{} / {}", format_bytes(total - free), format_bytes(total)),
+        (total - free) as f64 / total as f64,
+    ))
+}
+
+/* ## memory health */
+
+/// a plain word on whether memory is enough, going by what is still available
+/// and how much has gone to swap, which counts for less when swappiness invites it
+pub fn get_memory_health() -> DatumResult {
+    ensure_linux()?;
+    let meminfo = std::fs::read_to_string("/proc/meminfo")?;
+    let read = |key: &str| {
+        read_meminfo(&meminfo, key)
+            .ok_or_else(|| simple_error::simple_error!("no {} in meminfo", key))
+    };
+    let (total, available) = (read("MemTotal")?, read("MemAvailable")?);
+    let (swap_total, swap_free) = (read("SwapTotal")?, read("SwapFree")?);
+    let swappiness: u32 = std::fs::read_to_string("/proc/sys/vm/swappiness")?
+        .trim()
+        .parse()?;
+
+    let available = available as f64 / total as f64;
+    let swapped = if swap_total == 0 {
+        0.0
+    } else {
+        swap_total.saturating_sub(swap_free) as f64 / swap_total as f64
+    };
+    // an eager kernel swaps out idle pages long before memory runs short
+    let swap_allowance = if swappiness > 60 { 0.3 } else { 0.1 };
+    let health = if available < 0.1 || swapped > 0.5 {
+        "under pressure (consider more RAM)"
+    } else if available < 0.25 || swapped > swap_allowance {
+        "tight"
+    } else {
+        "healthy"
+    };
+    Ok(Datum::new("\u{f85a}", health).with_words(&[health]))
+}
+
+/* ## memory modules */
+
+fn parse_module_size(size: &str) -> Option<u64> {
+    let (amount, unit) = size.split_once(' ')?;
+    let amount: u64 = amount.parse().ok()?;
+    Some(match unit {
+        "kB" | "KB" => amount << 10,
+        "MB" => amount << 20,
+        "GB" => amount << 30,
+        "TB" => amount << 40,
+        _ => return None,
+    })
+}
+
+pub fn get_ram_info() -> DatumResult {
+    let dmidecode = read_command("dmidecode", &["-t", "memory"])?;
+    let (mut total, mut kind, mut speed) = (0, None, None);
+    for line in dmidecode.lines().map(str::trim) {
+        if let Some(size) = line.strip_prefix("Size: ") {
+            total += parse_module_size(size).unwrap_or(0);
+        } else if let Some(module) = line.strip_prefix("Type: ") {
+            if kind.is_none() && module != "Unknown" && module != "Other" {
+                kind = Some(module.to_owned());
+            }
+        } else if let Some(rate) = line.strip_prefix("Speed: ") {
+            speed = speed.or_else(|| rate.split(' ').next()?.parse::<u32>().ok());
+        }
+    }
+    if total == 0 {
+        simple_error::bail!("no memory modules");
+    }
+
+    let mut display = format_bytes(total);
+    if let Some(kind) = kind {
+        write!(display, " {kind}")?;
+        if let Some(speed) = speed {
+            write!(display, "-{speed}")?;
+        }
+    }
+    Ok(Datum::new("\u{f538}", &display))
+}
+
+/* ## desktop daemons */
+
+/// names of running processes, which linux cuts to their first fifteen bytes
+fn read_process_names() -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    match std::fs::read_dir("/proc") {
+        Ok(processes) => Ok(processes
+            .flatten()
+            .filter_map(|process| std::fs::read_to_string(process.path().join("comm")).ok())
+            .map(|name| name.trim().to_owned())
+            .collect()),
+        Err(_) => Ok(read_command("ps", &["-Ao", "comm="])?
+            .lines()
+            .map(|command| {
+                command
+                    .trim()
+                    .rsplit('/')
+                    .next()
+                    .unwrap_or_default()
+                    .to_owned()
+            })
+            .collect()),
+    }
+}
+
+fn find_process(candidates: &[&str]) -> StringResult {
+    let running = read_process_names()?;
+    candidates
+        .iter()
+        .find(|candidate| running.iter().any(|name| name == *candidate))
+        .map(|&found| found.to_owned())
+        .ok_or_else(|| simple_error::simple_error!("none running").into())
+}
+
+pub fn get_notification_daemon() -> DatumResult {
+    Ok(Datum::new(
+        "\u{f0f3}",
+        &find_process(&[
+            "dunst",
+            "mako",
+            "swaync",
+            "fnott",
+            "xfce4-notifyd",
+            "deadd-notificat",
+            "notification-da",
+            "notify-osd",
+        ])?,
+    ))
+}
+
+pub fn get_clipboard_manager() -> DatumResult {
+    Ok(Datum::new(
+        "\u{f0ea}",
+        &find_process(&[
+            "cliphist",
+            "clipman",
+            "copyq",
+            "clipmenud",
+            "greenclip",
+            "parcellite",
+            "clipit",
+            "klipper",
+        ])?,
+    ))
+}
+
+/* ## default browser */
+
+/// the bundle id launchservices hands https links to, safari when none is set
+fn read_mac_browser() -> StringResult {
+    let handlers = read_command(
+        "defaults",
+        &[
+            "read",
+            "com.apple.LaunchServices/com.apple.launchservices.secure",
+            "LSHandlers",
+        ],
+    )?;
+    Ok(handlers
+        .split('}')
+        .find(|handler| handler.contains("LSHandlerURLScheme = https;"))
+        .and_then(|handler| {
+            handler
+                .lines()
+                .find_map(|line| line.trim().strip_prefix("LSHandlerRoleAll = "))
+        })
+        .map_or("com.apple.safari", |id| {
+            id.trim_end_matches(';').trim_matches('"')
+        })
+        .to_owned())
+}
+
+pub fn get_default_browser() -> DatumResult {
+    let browser = match nix::sys::utsname::uname().sysname() {
+        "Darwin" => read_mac_browser()?,
+        _ => read_command("xdg-settings", &["get", "default-web-browser"])?,
+    };
+    // both come as reverse domain names at worst, like `org.mozilla.firefox.desktop`
+    let browser = browser.trim().trim_end_matches(".desktop");
+    let browser = browser.rsplit('.').next().unwrap_or(browser);
+    if browser.is_empty() {
+        simple_error::bail!("no default browser");
+    }
+    Ok(Datum::new("\u{f0ac}", browser))
+}
+
+/* ## orphan packages */
+
+fn read_orphans() -> Result<usize, Box<dyn std::error::Error>> {
+    // pacman exits unsuccessfully when there are no orphans to list
+    if let Ok(output) = std::process::Command::new("pacman").arg("-Qtdq").output() {
+        return Ok(count_lines(&String::from_utf8(output.stdout)?));
+    }
+    Ok(read_command("apt-get", &["--dry-run", "autoremove"])?
+        .lines()
+        .filter(|line| line.starts_with("Remv "))
+        .count())
+}
+
+pub fn get_orphans() -> DatumResult {
+    Ok(Datum::new("\u{f1b3}", &read_orphans()?.to_string()).with_label("orphans"))
+}
+
+/* ## package mirrors */
+
+pub fn get_mirrors() -> DatumResult {
+    let mirrors = std::fs::read_to_string("/etc/pacman.d/mirrorlist")?
+        .lines()
+        .filter(|line| {
+            line.trim_start()
+                .strip_prefix("Server")
+                .is_some_and(|rest| rest.trim_start().starts_with('='))
+        })
+        .count();
+    Ok(Datum::counted("\u{f0ac}", mirrors, "mirror", "mirrors"))
+}
+
+/* ## temperature */
+
+#[derive(Clone, Copy)]
+pub enum TemperatureUnit {
+    Celsius,
+    Fahrenheit,
+}
+
+pub fn get_temperature(unit: TemperatureUnit) -> DatumResult {
+    let celsius = systemstat::System::new().cpu_temp()?;
+    Ok(Datum::new(
+        "\u{f2c9}",
+        &match unit {
+            TemperatureUnit::Celsius => format!("{celsius:.0}\u{b0}C"),
+            TemperatureUnit::Fahrenheit => format!("{:.0}\u{b0}F", celsius * 9.0 / 5.0 + 32.0),
+        },
+    )
+    // yellow from 50°C and red from 80°C
+    .with_status(f64::from(celsius) / 100.0))
+}
+
+/* ## disks */
+
+/// the whole disk a mounted device lives on, so `nvme0n1p2` reads as `nvme0n1`
+fn read_disk_name(device: &str) -> StringResult {
+    let device = std::fs::canonicalize(device)?;
+    let name = device
+        .file_name()
+        .and_then(std::ffi::OsStr::to_str)
+        .ok_or_else(|| simple_error::simple_error!("unrecognised device"))?;
+    let block = std::fs::canonicalize(std::path::Path::new("/sys/class/block").join(name));
+    Ok(match block {
+        Ok(block) if block.join("partition").exists() => block
+            .parent()
+            .and_then(std::path::Path::file_name)
+            .and_then(std::ffi::OsStr::to_str)
+            .unwrap_or(name)
+            .to_owned(),
+        _ => name.to_owned(),
+    })
+}
+
+fn format_mount(mount: &systemstat::Filesystem) -> StringResult {
+    Ok(format!(
+        "{} {} / {}",
+        read_disk_name(&mount.fs_mounted_from)?,
+        format_bytes(mount.total.as_u64() - mount.free.as_u64()),
+        format_bytes(mount.total.as_u64()),
+    ))
+}
+
+fn mount_usage(mount: &systemstat::Filesystem) -> f64 {
+    (mount.total.as_u64() - mount.free.as_u64()) as f64 / mount.total.as_u64() as f64
+}
+
+pub fn get_disk() -> DatumResult {
+    let root = systemstat::System::new().mount_at("/")?;
+    Ok(Datum::new("\u{f0a0}", &format_mount(&root)?).with_status(mount_usage(&root)))
+}
+
+/// share of the root filesystem's inodes in use, which can run out well before its space
+pub fn get_inodes() -> DatumResult {
+    let root = systemstat::System::new().mount_at("/")?;
+    if root.files_total == 0 {
+        simple_error::bail!("no fixed inode count");
+    }
+    let used = root.files as f64 / root.files_total as f64;
+    Ok(Datum::new("\u{f0a0}", &format!("{:.0}%", used * 100.0))
+        .with_label("inodes")
+        .with_status(used))
+}
+
+/// the dm-crypt type somewhere beneath a block device, looking through lvm and the like,
+/// going by device mapper uuids such as `CRYPT-LUKS2-...`
+fn read_crypt_type(block: &std::path::Path) -> Option<String> {
+    if let Ok(uuid) = std::fs::read_to_string(block.join("dm/uuid")) {
+        if let Some(kind) = uuid.strip_prefix("CRYPT-") {
+            return Some(match kind.split('-').next().unwrap_or_default() {
+                "PLAIN" => "dm-crypt".to_owned(),
+                kind => kind.to_owned(),
+            });
+        }
+    }
+    std::fs::read_dir(block.join("slaves"))
+        .ok()?
+        .flatten()
+        .find_map(|slave| read_crypt_type(&slave.path()))
+}
+
+pub fn get_encryption() -> DatumResult {
+    ensure_linux()?;
+    let root = systemstat::System::new().mount_at("/")?;
+    let device = std::fs::canonicalize(&root.fs_mounted_from)?;
+    let name = device
+        .file_name()
+        .ok_or_else(|| simple_error::simple_error!("unrecognised device"))?;
+    let block = std::path::Path::new("/sys/class/block").join(name);
+    if !block.exists() {
+        simple_error::bail!("root is not on a block device");
+    }
+    Ok(Datum::new(
+        "\u{f023}",
+        &read_crypt_type(&block).unwrap_or_else(|| "none".to_owned()),
+    )
+    .with_label("encryption"))
+}
+
+/// the efi system partition or separate `/boot`, whichever is mounted on its own
+pub fn get_boot_disk() -> DatumResult {
+    let mounts = systemstat::System::new().mounts()?;
+    let boot = ["/boot/efi", "/efi", "/boot"]
+        .iter()
+        .find_map(|path| mounts.iter().find(|mount| mount.fs_mounted_on == *path))
+        .ok_or_else(|| simple_error::simple_error!("no separate boot mount"))?;
+    Ok(Datum::new("\u{f0a0}", &format_mount(boot)?)
+        .with_label("boot")
+        .with_status(mount_usage(boot)))
+}
+
+/* ## writeback */
+
+/// how often dirty pages are flushed and how far the root disk reads ahead
+pub fn get_writeback() -> DatumResult {
+    ensure_linux()?;
+    let centiseconds: u32 = std::fs::read_to_string("/proc/sys/vm/dirty_writeback_centisecs")?
+        .trim()
+        .parse()?;
+    let disk = read_disk_name(&systemstat::System::new().mount_at("/")?.fs_mounted_from)?;
+    let readahead: u64 =
+        match std::fs::read_to_string(format!("/sys/block/{disk}/queue/read_ahead_kb")) {
+            Ok(kibibytes) => kibibytes.trim().parse()?,
+            // blockdev counts in 512 byte sectors
+            Err(_) => {
+                read_command("blockdev", &["--getra", &format!("/dev/{disk}")])?
+                    .trim()
+                    .parse::<u64>()?
+                    / 2
+            }
+        };
+    Ok(Datum::new(
+        "\u{f0a0}",
+        &format!(
+            "{}s, readahead {}",
+            f64::from(centiseconds) / 100.0,
+            format_bytes(readahead << 10)
+        ),
+    )
+    .with_label("writeback"))
+}
+
+/* ## trim */
+
+pub fn get_trim() -> DatumResult {
+    ensure_linux()?;
+    let discard = std::fs::read_to_string("/proc/mounts")?
+        .lines()
+        .filter_map(|line| line.split_whitespace().nth(3))
+        .any(|options| options.split(',').any(|option| option == "discard"));
+    let status = if discard {
+        "enabled (discard)"
+    } else {
+        // is-active exits unsuccessfully for an inactive timer, which still says something
+        let output = std::process::Command::new("systemctl")
+            .args(["is-active", "fstrim.timer"])
+            .output()?;
+        if output.status.success() {
+            "enabled (fstrim.timer)"
+        } else if output.stdout.starts_with(b"inactive") {
+            "disabled"
+        } else {
+            simple_error::bail!("no fstrim.timer to check");
+        }
+    };
+    Ok(Datum::new("\u{f0a0}", status).with_label("trim"))
+}
+
+/* ## swap */
+
+struct SwapArea {
+    name: String,
+    bytes: u64,
+    priority: i32,
+}
+
+fn read_swaps() -> Result<Vec<SwapArea>, Box<dyn std::error::Error>> {
+    let areas: Vec<SwapArea> = std::fs::read_to_string("/proc/swaps")?
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let columns: Vec<&str> = line.split_whitespace().collect();
+            Some(SwapArea {
+                name: columns.first()?.trim_start_matches("/dev/").to_owned(),
+                bytes: columns.get(2)?.parse::<u64>().ok()? * 1024,
+                priority: columns.get(4)?.parse().ok()?,
+            })
+        })
+        .collect();
+    if areas.is_empty() {
+        simple_error::bail!("no swap");
+    }
+    Ok(areas)
+}
+
+/// the bracketed one of the compressors a zram device lists
+fn read_zram_algorithm(device: &str) -> Option<String> {
+    if !device.starts_with("zram") {
+        return None;
+    }
+    let algorithms = std::fs::read_to_string(format!("/sys/block/{device}/comp_algorithm")).ok()?;
+    let (_, active) = algorithms.split_once('[')?;
+    Some(active.split_once(']')?.0.to_owned())
+}
+
+/// swap areas in the order the kernel fills them, highest priority first
+pub fn get_swaps() -> DatumResult {
+    let mut areas = read_swaps()?;
+    areas.sort_by_key(|area| core::cmp::Reverse(area.priority));
+    Ok(Datum::new(
+        "\u{f021}",
+        &areas
+            .iter()
+            .map(|area| {
+                let swap = format!(
+                    "{}({}) {}",
+                    area.name,
+                    area.priority,
+                    format_bytes(area.bytes)
+                );
+                match read_zram_algorithm(&area.name) {
+                    Some(algorithm) => format!("{swap} ({algorithm})"),
+                    None => swap,
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(" > "),
+    ))
+}
+
+/* ## zswap */
+
+/// kibibytes behind a `/proc/meminfo` key
+fn read_meminfo(meminfo: &str, key: &str) -> Option<u64> {
+    meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix(key)?.strip_prefix(':'))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// what went into zswap and what it takes up, from meminfo or failing that debugfs
+fn read_zswap_sizes() -> Result<(u64, u64), Box<dyn std::error::Error>> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo")?;
+    if let (Some(stored), Some(compressed)) = (
+        read_meminfo(&meminfo, "Zswapped"),
+        read_meminfo(&meminfo, "Zswap"),
+    ) {
+        return Ok((stored, compressed));
+    }
+    let read = |name: &str| -> Result<u64, Box<dyn std::error::Error>> {
+        Ok(
+            std::fs::read_to_string(format!("/sys/kernel/debug/zswap/{name}"))?
+                .trim()
+                .parse()?,
+        )
+    };
+    let page_size = nix::unistd::sysconf(nix::unistd::SysconfVar::PAGE_SIZE)?.unwrap_or(4096);
+    Ok((
+        read("stored_pages")? * page_size as u64 / 1024,
+        read("pool_total_size")? / 1024,
+    ))
+}
+
+pub fn get_zswap() -> DatumResult {
+    ensure_linux()?;
+    if std::fs::read_to_string("/sys/module/zswap/parameters/enabled")?.trim() != "Y" {
+        simple_error::bail!("zswap disabled");
+    }
+    let (stored, compressed) = read_zswap_sizes()?;
+    if compressed == 0 {
+        simple_error::bail!("zswap empty");
+    }
+    let ratio = stored as f64 / compressed as f64;
+    Ok(Datum::new("\u{f0a0}", &format!("{ratio:.1}:1")).with_label("zswap"))
+}
+
+/* ## audio */
+
+pub fn get_sample_rate() -> DatumResult {
+    let info = read_command("pactl", &["info"])?;
+    let sink = info
+        .lines()
+        .find_map(|line| line.strip_prefix("Default Sink:"))
+        .ok_or_else(|| simple_error::simple_error!("no default sink"))?
+        .trim();
+    // index, name, module, format, channels, rate and state
+    let rate: f64 = read_command("pactl", &["list", "sinks", "short"])?
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<&str>>())
+        .find(|columns| columns.get(1) == Some(&sink))
+        .and_then(|columns| columns.get(5)?.strip_suffix("Hz")?.parse().ok())
+        .ok_or_else(|| simple_error::simple_error!("no sample rate for {}", sink))?;
+    Ok(Datum::new("\u{f028}", &format!("{}kHz", rate / 1000.0)).with_label("audio"))
+}
+
+/// pipewire's quantum, a forced one winning over the default, and the latency it makes
+pub fn get_audio_buffer() -> DatumResult {
+    let settings = read_command("pw-metadata", &["-n", "settings"])?;
+    // update: id:0 key:'clock.quantum' value:'1024' type:''
+    let read = |key: &str| -> Option<u32> {
+        settings.lines().find_map(|line| {
+            line.split_once(&format!("key:'{key}' value:'"))?
+                .1
+                .split_once('\'')?
+                .0
+                .parse()
+                .ok()
+        })
+    };
+    let quantum = read("clock.force-quantum")
+        .filter(|&forced| forced > 0)
+        .or_else(|| read("clock.quantum"))
+        .ok_or_else(|| simple_error::simple_error!("no pipewire quantum"))?;
+    Ok(Datum::new(
+        "\u{f028}",
+        &match read("clock.force-rate")
+            .filter(|&forced| forced > 0)
+            .or_else(|| read("clock.rate"))
+        {
+            Some(rate) => format!(
+                "{quantum} ({:.1}ms)",
+                f64::from(quantum) * 1000.0 / f64::from(rate)
+            ),
+            None => quantum.to_string(),
+        },
+    )
+    .with_label("audio buffer"))
+}
+
+/* ## hugepages */
+
+pub fn get_hugepages() -> DatumResult {
+    ensure_linux()?;
+    let meminfo = std::fs::read_to_string("/proc/meminfo")?;
+    let (total, size) = (
+        read_meminfo(&meminfo, "HugePages_Total"),
+        read_meminfo(&meminfo, "Hugepagesize"),
+    );
+    match (total, size) {
+        (Some(total), Some(size)) if total > 0 => Ok(Datum::new(
+            "\u{f0a0}",
+            &format!("{total} x {}", format_bytes(size * 1024)),
+        )
+        .with_label("hugepages")),
+        _ => simple_error::bail!("no hugepages configured"),
+    }
+}
+
+/* ## memory compression */
+
+/// zram swapped onto and zswap in front of it compress the same pages twice over
+pub fn get_compression() -> DatumResult {
+    ensure_linux()?;
+    let zram =
+        read_swaps().is_ok_and(|areas| areas.iter().any(|area| area.name.starts_with("zram")));
+    let zswap = std::fs::read_to_string("/sys/module/zswap/parameters/enabled")
+        .is_ok_and(|enabled| enabled.trim() == "Y");
+    let compression = match (zram, zswap) {
+        (true, true) => "zram+zswap (misconfigured)",
+        (true, false) => "zram only",
+        (false, true) => "zswap only",
+        (false, false) => "none",
+    };
+    Ok(Datum::new("\u{f0a0}", compression).with_label("compression"))
+}
+
+/* ## fonts */
+
+pub fn get_font_count() -> DatumResult {
+    let fonts = count_lines(&read_command("fc-list", &[])?);
+    Ok(Datum::counted("\u{f031}", fonts, "font", "fonts"))
+}
+
+/* ## gateway */
+
+#[cfg(feature = "network")]
+fn read_gateway() -> StringResult {
+    match read_default_route() {
+        Ok((_, gateway)) => {
+            let address = u32::from_str_radix(&gateway, 16)?;
+            // the kernel prints the network-order address as a native integer
+            Ok(std::net::Ipv4Addr::from(address.to_ne_bytes()).to_string())
+        }
+        Err(_) => read_command("route", &["-n", "get", "default"])?
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("gateway:"))
+            .map(|gateway| gateway.trim().to_owned())
+            .ok_or_else(|| simple_error::simple_error!("no default route").into()),
+    }
+}
+
+#[cfg(feature = "network")]
+pub fn get_gateway() -> DatumResult {
+    Ok(Datum::new("\u{f0e8}", &read_gateway()?))
+}
+
+/* ## battery */
+
+#[cfg(feature = "battery")]
+pub fn get_battery() -> DatumResult {
+    let system = systemstat::System::new();
+    let battery = system.battery_life()?;
+    let mut display = format!("{:.0}%", battery.remaining_capacity * 100.0);
+    if system.on_ac_power()? {
+        write!(display, " (charging)")?;
+    }
+    Ok(Datum::new("\u{f240}", &display)
+        .with_words(&["charging"])
+        .with_status(1.0 - f64::from(battery.remaining_capacity)))
+}
+
+/* ## gpu memory */
+
+#[cfg(feature = "gpu")]
+pub fn get_vram() -> DatumResult {
+    let cards: Vec<u64> = read_command(
+        "nvidia-smi",
+        &["--query-gpu=memory.total", "--format=csv,noheader,nounits"],
+    )?
+    .lines()
+    .filter_map(|mebibytes| mebibytes.trim().parse().ok())
+    .collect();
+    if cards.is_empty() {
+        simple_error::bail!("no nvidia gpus");
+    }
+    let noun = plural(cards.len(), "GPU", "GPUs");
+    Ok(Datum::new(
+        "\u{f1b2}",
+        &format!(
+            "{} {noun}, {} total VRAM",
+            cards.len(),
+            format_bytes(cards.iter().sum::<u64>() << 20),
+        ),
+    )
+    .with_words(&[noun, "total VRAM"]))
+}
+
+/* ## cpu features */
+
+/// cpuinfo flags worth knowing about before reaching for `-C target-cpu=native`,
+/// under the names rust gives them as target features
+const RUST_FEATURES: &[(&str, &str)] = &[
+    ("sse4_2", "sse4.2"),
+    ("avx", "avx"),
+    ("avx2", "avx2"),
+    ("fma", "fma"),
+    ("bmi2", "bmi2"),
+    ("avx512f", "avx512f"),
+    ("asimd", "neon"),
+    ("sve", "sve"),
+    ("sve2", "sve2"),
+];
+
+pub fn get_cpu_features() -> DatumResult {
+    let cpuinfo = std::fs::read_to_string("/proc/cpuinfo")?;
+    // x86 lists "flags", arm lists "Features"
+    let flags: Vec<&str> = cpuinfo
+        .lines()
+        .find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            matches!(key.trim(), "flags" | "Features").then_some(value)
+        })
+        .ok_or_else(|| simple_error::simple_error!("no cpu flags"))?
+        .split_whitespace()
+        .collect();
+    let features: Vec<&str> = RUST_FEATURES
+        .iter()
+        .filter(|(flag, _)| flags.contains(flag))
+        .map(|&(_, feature)| feature)
+        .collect();
+    if features.is_empty() {
+        simple_error::bail!("no notable cpu features");
+    }
+    Ok(Datum::new("\u{e7a8}", &features.join(" ")))
+}
+
+/* ## cpu signature */
+
+/// family, model and stepping, the numbers errata and microcode go by
+pub fn get_cpu_signature() -> DatumResult {
+    let cpuinfo = std::fs::read_to_string("/proc/cpuinfo")?;
+    let read = |name: &str| {
+        cpuinfo
+            .lines()
+            .find_map(|line| {
+                let (key, value) = line.split_once(':')?;
+                (key.trim() == name).then(|| value.trim())
+            })
+            .ok_or_else(|| simple_error::simple_error!("no cpu {}", name))
+    };
+    Ok(Datum::new(
+        "\u{f2db}",
+        &format!(
+            "family {}, model {}, stepping {}",
+            read("cpu family")?,
+            read("model")?,
+            read("stepping")?
+        ),
+    ))
+}
+
+/* ## cpu vulnerabilities */
+
+pub fn get_vulnerabilities() -> DatumResult {
+    ensure_linux()?;
+    let (mut vulnerable, mut mitigated) = (0, 0);
+    for entry in std::fs::read_dir("/sys/devices/system/cpu/vulnerabilities")?.flatten() {
+        let status = std::fs::read_to_string(entry.path()).unwrap_or_default();
+        if status.starts_with("Vulnerable") {
+            vulnerable += 1;
+        } else if status.starts_with("Mitigation") {
+            mitigated += 1;
+        }
+    }
+    let status = if vulnerable > 0 { 1.0 } else { 0.0 };
+    Ok(Datum::new(
+        "\u{f132}",
+        &format!("{vulnerable} vulnerable, {mitigated} mitigated"),
+    )
+    .with_label("mitigations")
+    .with_status(status))
+}
+
+/* ## power profile */
+
+pub fn get_power_profile() -> DatumResult {
+    let profile = read_command("powerprofilesctl", &["get"]).or_else(|_| {
+        read_command("tuned-adm", &["active"]).and_then(|active| {
+            Ok(active
+                .lines()
+                .find_map(|line| line.strip_prefix("Current active profile:"))
+                .ok_or_else(|| simple_error::simple_error!("no active tuned profile"))?
+                .to_owned())
+        })
+    })?;
+    Ok(Datum::new("\u{f0e7}", profile.trim()))
+}
+
+/* ## platform profile */
+
+/// the firmware's own power profile, which laptops may expose apart from the daemon's
+pub fn get_platform_profile() -> DatumResult {
+    let profile = std::fs::read_to_string("/sys/firmware/acpi/platform_profile")?;
+    Ok(Datum::new("\u{f0e4}", profile.trim()))
+}
+
+/* ## battery time */
+
+#[cfg(feature = "battery")]
+pub fn get_battery_time() -> DatumResult {
+    let system = systemstat::System::new();
+    let remaining = system.battery_life()?.remaining_time;
+    if remaining.as_secs() < 60 {
+        simple_error::bail!("no battery estimate");
+    }
+    // systemstat estimates time to full while on ac and time to empty otherwise
+    let direction = if system.on_ac_power()? {
+        "until full"
+    } else {
+        "remaining"
+    };
+    Ok(Datum::new("\u{f240}", format_uptime(remaining)?.trim_end()).with_suffix(direction))
+}
+
+/* ## idle states */
+
+/// the last enabled of cpu0's idle states, which the kernel lists shallowest first
+pub fn get_idle_state() -> DatumResult {
+    let mut states: Vec<(usize, std::path::PathBuf)> =
+        std::fs::read_dir("/sys/devices/system/cpu/cpu0/cpuidle")?
+            .flatten()
+            .filter_map(|entry| {
+                let index = entry
+                    .file_name()
+                    .to_str()?
+                    .strip_prefix("state")?
+                    .parse()
+                    .ok()?;
+                Some((index, entry.path()))
+            })
+            .collect();
+    states.sort();
+    let name = states
+        .iter()
+        .rev()
+        .filter(|(_, state)| {
+            std::fs::read_to_string(state.join("disable"))
+                .map_or(true, |disable| disable.trim() == "0")
+        })
+        .find_map(|(_, state)| std::fs::read_to_string(state.join("name")).ok())
+        .ok_or_else(|| simple_error::simple_error!("no idle states"))?;
+    Ok(Datum::new("\u{f2db}", name.trim()).with_label("idle"))
+}
+
+/* ## kubernetes */
+
+/// namespace and pod from the downward api, else from the service account and hostname
+pub fn get_kubernetes() -> DatumResult {
+    std::env::var("KUBERNETES_SERVICE_HOST")
+        .map_err(|_| simple_error::simple_error!("not in kubernetes"))?;
+    let namespace = match std::env::var("POD_NAMESPACE") {
+        Ok(namespace) => namespace,
+        Err(_) => {
+            std::fs::read_to_string("/var/run/secrets/kubernetes.io/serviceaccount/namespace")?
+                .trim()
+                .to_owned()
+        }
+    };
+    let pod = match std::env::var("POD_NAME") {
+        Ok(pod) => pod,
+        Err(_) => read_host()?,
+    };
+    Ok(Datum::new("\u{f1b3}", &format!("{namespace}/{pod}")).with_label("k8s"))
+}
+
+/* ## pci devices */
+
+pub fn get_pci_devices() -> DatumResult {
+    let count = match std::fs::read_dir("/sys/bus/pci/devices") {
+        Ok(devices) => devices.count(),
+        Err(_) => read_command("lspci", &[])?.lines().count(),
+    };
+    if count == 0 {
+        simple_error::bail!("no pci devices");
+    }
+    Ok(Datum::counted(
+        "\u{f2db}",
+        count,
+        "pci device",
+        "pci devices",
+    ))
+}
+
+/* ## cpu usage */
+
+pub fn get_cpu_usage() -> DatumResult {
+    let load = systemstat::System::new().cpu_load_aggregate()?;
+    std::thread::sleep(core::time::Duration::from_millis(200));
+    let usage = 1.0 - f64::from(load.done()?.idle);
+    Ok(Datum::with_usage(
+        "\u{f4bc}",
+        &format!("{:.0}%", usage * 100.0),
+        usage,
+    ))
+}
+
+/* ## kernel command line */
+
+pub fn get_cmdline() -> DatumResult {
+    Ok(Datum::new(
+        "\u{f013}",
+        &truncate(
+            &std::fs::read_to_string("/proc/cmdline")?
+                .split_whitespace()
+                .filter(|parameter| {
+                    !parameter.starts_with("BOOT_IMAGE=") && !parameter.starts_with("initrd=")
+                })
+                .collect::<Vec<&str>>()
+                .join(" "),
+            64,
+        ),
+    ))
+}
+
+/* ## disk activity */
+
+pub fn get_disk_io() -> DatumResult {
+    let system = systemstat::System::new();
+    let device = std::fs::canonicalize(system.mount_at("/")?.fs_mounted_from)?;
+    let device = device
+        .file_name()
+        .and_then(std::ffi::OsStr::to_str)
+        .ok_or_else(|| simple_error::simple_error!("unrecognised root device"))?;
+    let stats = system
+        .block_device_statistics()?
+        .remove(device)
+        .ok_or_else(|| simple_error::simple_error!("no statistics for root device"))?;
+
+    // diskstats counts in 512 byte sectors regardless of the device's own sector size
+    Ok(Datum::new(
+        "\u{f0a0}",
+        &format!(
+            "R {} W {}",
+            format_bytes(stats.read_sectors as u64 * 512),
+            format_bytes(stats.write_sectors as u64 * 512),
+        ),
+    ))
+}
+
+/* ## sessions */
+
+pub fn get_sessions() -> DatumResult {
+    let sessions = read_command("loginctl", &["list-sessions", "--no-legend"])
+        .or_else(|_| read_command("who", &[]))?;
+    Ok(Datum::counted(
+        "\u{f0c0}",
+        count_lines(&sessions),
+        "session",
+        "sessions",
+    ))
+}
+
+/* ## virtual consoles */
+
+pub fn get_consoles() -> DatumResult {
+    let sessions = read_command("loginctl", &["list-sessions", "--no-legend"])
+        .or_else(|_| read_command("who", &[]))?;
+    let mut consoles: Vec<&str> = sessions
+        .split_whitespace()
+        .filter(|word| {
+            word.strip_prefix("tty").is_some_and(|number| {
+                !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit())
+            })
+        })
+        .collect();
+    consoles.sort_unstable();
+    consoles.dedup();
+    Ok(Datum::counted(
+        "\u{f120}",
+        consoles.len(),
+        "console",
+        "consoles",
+    ))
+}
+
+/* ## services */
+
+pub fn get_services() -> DatumResult {
+    // systemd's own check for having booted the machine
+    if !std::path::Path::new("/run/systemd/system").exists() {
+        simple_error::bail!("not running systemd");
+    }
+    let services = read_command(
+        "systemctl",
+        &[
+            "list-units",
+            "--type=service",
+            "--state=running",
+            "--no-legend",
+        ],
+    )?;
+    Ok(Datum::counted(
+        "\u{f013}",
+        count_lines(&services),
+        "service",
+        "services",
+    ))
+}
+
 /* ## uptime */
 
-pub fn get_uptime() -> StringResult {
-    format_uptime(systemstat::System::new().uptime()?)
+/// what uptime can be measured against instead of being shown as a duration
+#[derive(Clone, Copy)]
+pub enum UptimePeriod {
+    Month,
+    Install,
+}
+
+/// first day of the utc month containing `days` since the epoch, counted the same way;
+/// after Howard Hinnant's `civil_from_days` and `days_from_civil`
+fn month_start(days: i64) -> i64 {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    // day_of_year counts from march, so stepping back to the first only drops the day of month
+    let day_of_month = day_of_year - (153 * month_index + 2) / 5;
+    era * 146_097 + day_of_era - day_of_month - 719_468
+}
+
+fn format_uptime_share(uptime: core::time::Duration, period: UptimePeriod) -> StringResult {
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH)?;
+    let (elapsed, label) = match period {
+        UptimePeriod::Month => {
+            let start = month_start(now.as_secs() as i64 / (24 * 60 * 60)) * 24 * 60 * 60;
+            (
+                now.saturating_sub(core::time::Duration::from_secs(start as u64)),
+                "this month",
+            )
+        }
+        UptimePeriod::Install => (
+            std::fs::metadata("/")?.created()?.elapsed()?,
+            "since install",
+        ),
+    };
+    let share = 100.0 * uptime.min(elapsed).as_secs_f64() / elapsed.as_secs_f64().max(1.0);
+    Ok(format!("{share:.1}% {label}"))
+}
+
+/// the longest uptime seen so far, raised to `uptime` if that beats it;
+/// a record that cannot be saved is still shown
+fn read_uptime_record(
+    uptime: core::time::Duration,
+) -> Result<core::time::Duration, Box<dyn std::error::Error>> {
+    let dir = cache_dir()?;
+    let path = dir.join("uptime-record");
+    let record = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|seconds| seconds.trim().parse().ok())
+        .map_or(core::time::Duration::ZERO, core::time::Duration::from_secs);
+    if uptime <= record {
+        return Ok(record);
+    }
+    let _ = std::fs::create_dir_all(&dir)
+        .and_then(|()| std::fs::write(&path, uptime.as_secs().to_string()));
+    Ok(uptime)
+}
+
+/// time since boot, or since `since` was last modified when given a marker file,
+/// optionally as a share of `period`
+pub fn get_uptime(
+    since: Option<&std::path::Path>,
+    period: Option<UptimePeriod>,
+    record: bool,
+) -> DatumResult {
+    let uptime = match since {
+        Some(marker) => std::fs::metadata(marker)?.modified()?.elapsed()?,
+        None => systemstat::System::new().uptime()?,
+    };
+    let mut value = match period {
+        Some(period) => format_uptime_share(uptime, period)?,
+        None => format_uptime(uptime)?,
+    };
+    // a marker file's age is no uptime to hold a record for
+    if record && since.is_none() {
+        let record = read_uptime_record(uptime)?;
+        value = format!(
+            "{} (record {})",
+            value.trim_end(),
+            format_uptime(record)?.trim_end()
+        );
+    }
+    Ok(Datum::new("\u{f64f}", &value).with_words(&["this month", "since install", "record"]))
 }
 
 /* ## terminal colours */
 
-pub fn get_colours() -> (String, String) {
-    (
-        (30_i32..38_i32)
-            .map(|i| format!("\x1b[{i}m\u{2b23}"))
+/// both rows of swatches, each followed by its palette index when `labelled`
+pub fn get_colours(style: &Style, labelled: bool) -> (String, String) {
+    let swatches = |offset: usize, colours: &[String]| {
+        colours
+            .iter()
+            .enumerate()
+            .map(|(index, colour)| {
+                if labelled {
+                    format!("{colour}\u{2b23}{RESET}{:<2}", offset + index)
+                } else {
+                    format!("{colour}\u{2b23}")
+                }
+            })
             .collect::<Vec<String>>()
-            .join(" "),
-        format!(
-            " {}",
-            (90_i32..98_i32)
-                .map(|i| format!("\x1b[{i}m\u{2b23}"))
-                .collect::<Vec<String>>()
-                .join(" ")
-        ),
+            .join(" ")
+            .trim_end()
+            .to_owned()
+    };
+    let (normal, bright) = style.palette.split_at(8);
+    let prefix = &style.prefix;
+    // the bright row sits half a swatch along from the normal one
+    (
+        format!("{prefix}{}", swatches(0, normal)),
+        format!("{prefix} {}", swatches(8, bright)),
     )
 }
+
+/* ## workspaces */
+
+fn read_i3_workspaces(program: &str) -> Result<(String, usize), Box<dyn std::error::Error>> {
+    let json = read_command(program, &["-t", "get_workspaces", "-r"])?;
+    let numbers = json_values(&json, "num");
+    let focused = json_values(&json, "focused")
+        .into_iter()
+        .position(|focused| focused == "true")
+        .ok_or_else(|| simple_error::simple_error!("no focused workspace"))?;
+    Ok((
+        numbers
+            .get(focused)
+            .ok_or_else(|| simple_error::simple_error!("unrecognised workspace"))?
+            .to_string(),
+        numbers.len(),
+    ))
+}
+
+fn read_hyprland_workspaces() -> Result<(String, usize), Box<dyn std::error::Error>> {
+    Ok((
+        json_values(&read_command("hyprctl", &["activeworkspace", "-j"])?, "id")
+            .first()
+            .ok_or_else(|| simple_error::simple_error!("no active workspace"))?
+            .to_string(),
+        json_values(&read_command("hyprctl", &["workspaces", "-j"])?, "id").len(),
+    ))
+}
+
+pub fn get_workspaces() -> DatumResult {
+    let (active, count) = if std::env::var_os("SWAYSOCK").is_some() {
+        read_i3_workspaces("swaymsg")?
+    } else if std::env::var_os("I3SOCK").is_some() {
+        read_i3_workspaces("i3-msg")?
+    } else if std::env::var_os("HYPRLAND_INSTANCE_SIGNATURE").is_some() {
+        read_hyprland_workspaces()?
+    } else {
+        simple_error::bail!("unrecognised window manager")
+    };
+    Ok(Datum::new("\u{f009}", &format!("{active}/{count}")))
+}
+
+/* ## plugins */
+
+/// the first line each executable in `dir` prints, in name order, split into
+/// `label: value` or labelled after the script; any that fail, print nothing
+/// or take more than a couple of seconds are left out
+pub fn get_plugins(dir: &std::path::Path) -> Vec<Datum> {
+    use std::io::Read;
+    use std::os::unix::fs::PermissionsExt;
+
+    const LIMIT: core::time::Duration = core::time::Duration::from_secs(2);
+
+    let mut scripts: Vec<_> = match std::fs::read_dir(dir) {
+        Ok(entries) => entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.metadata()
+                    .is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+            })
+            .collect(),
+        Err(_) => return Vec::new(),
+    };
+    scripts.sort();
+    scripts
+        .iter()
+        .filter_map(|script| {
+            let mut child = std::process::Command::new(script)
+                .stdin(std::process::Stdio::null())
+                .stdout(std::process::Stdio::piped())
+                .stderr(std::process::Stdio::null())
+                .spawn()
+                .ok()?;
+            if !wait_within(&mut child, LIMIT).ok()??.success() {
+                return None;
+            }
+            let mut stdout = String::new();
+            child.stdout.take()?.read_to_string(&mut stdout).ok()?;
+            let line = stdout.lines().find(|line| !line.trim().is_empty())?;
+            let (label, value) = match line.split_once(": ") {
+                Some((label, value)) if !label.trim().is_empty() => (label.trim(), value),
+                _ => (script.file_stem()?.to_str()?, line),
+            };
+            Some(Datum {
+                label: Some(std::borrow::Cow::Owned(label.to_owned())),
+                ..Datum::new("\u{f12e}", value.trim())
+            })
+        })
+        .collect()
+}
+
+/* ## updates */
+
+fn parse_version(version: &str) -> Vec<u64> {
+    version
+        .split('.')
+        .map_while(|part| part.parse().ok())
+        .collect()
+}
+
+/// the newest release on crates.io, if it is newer than this build;
+/// only ever reads the cached answer and leaves refreshing it to a detached curl
+pub fn check_update() -> StringResult {
+    const DAY: core::time::Duration = core::time::Duration::from_secs(24 * 60 * 60);
+
+    let dir = cache_dir()?;
+    let path = dir.join("latest-release.json");
+    let stale = std::fs::metadata(&path)
+        .and_then(|metadata| metadata.modified())
+        .map_or(true, |modified| {
+            modified.elapsed().map_or(true, |age| age > DAY)
+        });
+    if stale {
+        std::fs::create_dir_all(&dir)?;
+        let _refresh = std::process::Command::new("curl")
+            .args(["-sf", "-m", "5", "-A"])
+            .arg(concat!("orhfetch/", env!("CARGO_PKG_VERSION")))
+            .arg("-o")
+            .arg(&path)
+            .arg("https://crates.io/api/v1/crates/orhfetch")
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn()?;
+    }
+
+    let release = std::fs::read_to_string(&path)?;
+    let latest = json_values(&release, "max_stable_version")
+        .first()
+        .ok_or_else(|| simple_error::simple_error!("unrecognised crates.io response"))?
+        .trim_matches('"')
+        .to_owned();
+    if parse_version(&latest) > parse_version(env!("CARGO_PKG_VERSION")) {
+        Ok(latest)
+    } else {
+        simple_error::bail!("up to date")
+    }
+}
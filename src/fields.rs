@@ -0,0 +1,573 @@
+use crate::config::Config;
+use crate::data::{
+    get_audio_buffer, get_boot_disk, get_boot_mode, get_clipboard_manager, get_cmdline,
+    get_compression, get_consoles, get_cpu, get_cpu_features, get_cpu_signature, get_cpu_usage,
+    get_default_browser, get_disk, get_disk_io, get_displays_count, get_dotfiles, get_encryption,
+    get_font_count, get_hostname, get_hugepages, get_idle_state, get_inodes, get_kernels,
+    get_kubernetes, get_libc, get_memory, get_memory_health, get_mirrors, get_notification_daemon,
+    get_orphans, get_os, get_pci_devices, get_platform_profile, get_power_profile, get_preempt,
+    get_ram_info, get_sample_rate, get_scale, get_services, get_sessions, get_shell,
+    get_shell_framework, get_shell_startup, get_swaps, get_temperature, get_threads,
+    get_top_process, get_trim, get_uptime, get_userland, get_vulnerabilities, get_workspaces,
+    get_writeback, get_zswap, is_headless, is_laptop, DatumResult,
+};
+#[cfg(feature = "battery")]
+use crate::data::{get_battery, get_battery_time};
+#[cfg(feature = "network")]
+use crate::data::{get_connection_type, get_firewall, get_forwarding, get_gateway};
+#[cfg(feature = "gpu")]
+use crate::data::{get_gpu, get_mesa, get_vram};
+
+/* # field registry */
+
+/// sections of the tree layout, in the order they are printed
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Group {
+    Hardware,
+    Software,
+    System,
+    Desktop,
+    #[cfg(feature = "network")]
+    Network,
+}
+
+impl Group {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Hardware => "Hardware",
+            Self::Software => "Software",
+            Self::System => "System",
+            Self::Desktop => "Desktop",
+            #[cfg(feature = "network")]
+            Self::Network => "Network",
+        }
+    }
+}
+
+pub struct Field {
+    pub name: &'static str,
+    pub group: Group,
+    /// may give away who or where the machine is, so `--anon` scrubs it
+    pub sensitive: bool,
+    /// worth refetching on every refresh in watch mode
+    pub volatile: bool,
+    pub get: fn(&Config) -> DatumResult,
+}
+
+pub const FIELDS: &[Field] = &[
+    Field {
+        name: "hostname",
+        group: Group::System,
+        sensitive: false,
+        volatile: false,
+        get: |config| get_hostname(config.anon),
+    },
+    Field {
+        name: "os",
+        group: Group::Software,
+        sensitive: false,
+        volatile: false,
+        get: |config| get_os(config.os_base),
+    },
+    Field {
+        name: "shell",
+        group: Group::Software,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_shell(),
+    },
+    Field {
+        name: "uptime",
+        group: Group::System,
+        sensitive: false,
+        volatile: true,
+        get: |config| {
+            get_uptime(
+                config.uptime_since.as_deref(),
+                config.uptime_period,
+                config.uptime_record,
+            )
+        },
+    },
+    Field {
+        name: "workspaces",
+        group: Group::Desktop,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_workspaces(),
+    },
+    Field {
+        name: "cmdline",
+        group: Group::Software,
+        sensitive: true,
+        volatile: false,
+        get: |_| get_cmdline(),
+    },
+    Field {
+        name: "disk_io",
+        group: Group::System,
+        sensitive: false,
+        volatile: true,
+        get: |_| get_disk_io(),
+    },
+    Field {
+        name: "sessions",
+        group: Group::System,
+        sensitive: false,
+        volatile: true,
+        get: |_| get_sessions(),
+    },
+    Field {
+        name: "boot_mode",
+        group: Group::Hardware,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_boot_mode(),
+    },
+    #[cfg(feature = "gpu")]
+    Field {
+        name: "mesa",
+        group: Group::Software,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_mesa(),
+    },
+    Field {
+        name: "scale",
+        group: Group::Desktop,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_scale(),
+    },
+    Field {
+        name: "kernels",
+        group: Group::Software,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_kernels(),
+    },
+    Field {
+        name: "threads",
+        group: Group::System,
+        sensitive: false,
+        volatile: true,
+        get: |_| get_threads(),
+    },
+    #[cfg(feature = "network")]
+    Field {
+        name: "connection",
+        group: Group::Network,
+        sensitive: false,
+        volatile: true,
+        get: |_| get_connection_type(),
+    },
+    Field {
+        name: "top_process",
+        group: Group::System,
+        sensitive: false,
+        volatile: true,
+        get: |_| get_top_process(),
+    },
+    #[cfg(feature = "network")]
+    Field {
+        name: "firewall",
+        group: Group::Network,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_firewall(),
+    },
+    Field {
+        name: "displays",
+        group: Group::Hardware,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_displays_count(),
+    },
+    Field {
+        name: "ram",
+        group: Group::Hardware,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_ram_info(),
+    },
+    Field {
+        name: "notifications",
+        group: Group::Desktop,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_notification_daemon(),
+    },
+    Field {
+        name: "clipboard",
+        group: Group::Desktop,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_clipboard_manager(),
+    },
+    Field {
+        name: "mirrors",
+        group: Group::Software,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_mirrors(),
+    },
+    Field {
+        name: "temperature",
+        group: Group::Hardware,
+        sensitive: false,
+        volatile: true,
+        get: |config| get_temperature(config.temperature_unit),
+    },
+    Field {
+        name: "disk",
+        group: Group::Hardware,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_disk(),
+    },
+    Field {
+        name: "boot_disk",
+        group: Group::Hardware,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_boot_disk(),
+    },
+    Field {
+        name: "swaps",
+        group: Group::System,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_swaps(),
+    },
+    Field {
+        name: "fonts",
+        group: Group::Desktop,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_font_count(),
+    },
+    #[cfg(feature = "network")]
+    Field {
+        name: "gateway",
+        group: Group::Network,
+        sensitive: true,
+        volatile: false,
+        get: |_| get_gateway(),
+    },
+    #[cfg(feature = "battery")]
+    Field {
+        name: "battery",
+        group: Group::Hardware,
+        sensitive: false,
+        volatile: true,
+        get: |_| get_battery(),
+    },
+    #[cfg(feature = "gpu")]
+    Field {
+        name: "vram",
+        group: Group::Hardware,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_vram(),
+    },
+    Field {
+        name: "cpu_features",
+        group: Group::Hardware,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_cpu_features(),
+    },
+    #[cfg(feature = "battery")]
+    Field {
+        name: "battery_time",
+        group: Group::Hardware,
+        sensitive: false,
+        volatile: true,
+        get: |_| get_battery_time(),
+    },
+    Field {
+        name: "cpu_usage",
+        group: Group::Hardware,
+        sensitive: false,
+        volatile: true,
+        get: |_| get_cpu_usage(),
+    },
+    Field {
+        name: "inodes",
+        group: Group::Hardware,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_inodes(),
+    },
+    Field {
+        name: "cpu",
+        group: Group::Hardware,
+        sensitive: false,
+        volatile: false,
+        get: |config| get_cpu(config.vendor_icons),
+    },
+    #[cfg(feature = "gpu")]
+    Field {
+        name: "gpu",
+        group: Group::Hardware,
+        sensitive: false,
+        volatile: false,
+        get: |config| get_gpu(config.vendor_icons),
+    },
+    Field {
+        name: "services",
+        group: Group::System,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_services(),
+    },
+    Field {
+        name: "libc",
+        group: Group::Software,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_libc(),
+    },
+    Field {
+        name: "zswap",
+        group: Group::System,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_zswap(),
+    },
+    Field {
+        name: "shell_startup",
+        group: Group::Software,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_shell_startup(),
+    },
+    Field {
+        name: "dotfiles",
+        group: Group::Software,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_dotfiles(),
+    },
+    Field {
+        name: "trim",
+        group: Group::Hardware,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_trim(),
+    },
+    Field {
+        name: "preempt",
+        group: Group::System,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_preempt(),
+    },
+    Field {
+        name: "encryption",
+        group: Group::Hardware,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_encryption(),
+    },
+    Field {
+        name: "power_profile",
+        group: Group::Hardware,
+        sensitive: false,
+        volatile: true,
+        get: |_| get_power_profile(),
+    },
+    Field {
+        name: "consoles",
+        group: Group::System,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_consoles(),
+    },
+    Field {
+        name: "sample_rate",
+        group: Group::Desktop,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_sample_rate(),
+    },
+    Field {
+        name: "hugepages",
+        group: Group::System,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_hugepages(),
+    },
+    Field {
+        name: "orphans",
+        group: Group::Software,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_orphans(),
+    },
+    Field {
+        name: "idle_state",
+        group: Group::Hardware,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_idle_state(),
+    },
+    Field {
+        name: "memory",
+        group: Group::Hardware,
+        sensitive: false,
+        volatile: true,
+        get: |config| get_memory(config.memory_verbose),
+    },
+    Field {
+        name: "compression",
+        group: Group::System,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_compression(),
+    },
+    #[cfg(feature = "network")]
+    Field {
+        name: "forwarding",
+        group: Group::Network,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_forwarding(),
+    },
+    Field {
+        name: "platform_profile",
+        group: Group::Hardware,
+        sensitive: false,
+        volatile: true,
+        get: |_| get_platform_profile(),
+    },
+    Field {
+        name: "pci",
+        group: Group::Hardware,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_pci_devices(),
+    },
+    Field {
+        name: "browser",
+        group: Group::Desktop,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_default_browser(),
+    },
+    Field {
+        name: "memory_health",
+        group: Group::System,
+        sensitive: false,
+        volatile: true,
+        get: |_| get_memory_health(),
+    },
+    Field {
+        name: "cpu_signature",
+        group: Group::Hardware,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_cpu_signature(),
+    },
+    Field {
+        name: "kubernetes",
+        group: Group::System,
+        sensitive: true,
+        volatile: false,
+        get: |_| get_kubernetes(),
+    },
+    Field {
+        name: "audio_buffer",
+        group: Group::Desktop,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_audio_buffer(),
+    },
+    Field {
+        name: "userland",
+        group: Group::Software,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_userland(),
+    },
+    Field {
+        name: "shell_framework",
+        group: Group::Software,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_shell_framework(),
+    },
+    Field {
+        name: "writeback",
+        group: Group::System,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_writeback(),
+    },
+    Field {
+        name: "vulnerabilities",
+        group: Group::Hardware,
+        sensitive: false,
+        volatile: false,
+        get: |_| get_vulnerabilities(),
+    },
+];
+
+/// stands in for every plugin, whose output is fetched all at once by `get_plugins`
+pub static PLUGIN: Field = Field {
+    name: "plugins",
+    group: Group::Software,
+    sensitive: false,
+    volatile: false,
+    get: |_| simple_error::bail!("plugins are fetched together"),
+};
+
+/// what to show without a configured list, depending on what kind of machine this is
+pub fn default_fields() -> &'static [&'static str] {
+    if is_laptop() {
+        &[
+            "hostname",
+            "os",
+            "shell",
+            "uptime",
+            "battery",
+            "temperature",
+        ]
+    } else if is_headless() {
+        &["hostname", "os", "shell", "uptime", "disk", "sessions"]
+    } else {
+        &["hostname", "os", "shell", "uptime", "temperature", "mesa"]
+    }
+}
+
+pub fn find_field(name: &str) -> Option<&'static Field> {
+    FIELDS.iter().find(|field| field.name == name)
+}
+
+/// a field's value, piped through its configured filter if it has one;
+/// a filter that fails leaves the value as it was
+pub fn fetch(config: &Config, field: &Field) -> DatumResult {
+    use std::io::Write;
+
+    let mut datum = (field.get)(config)?;
+    let Some((_, command)) = config.filters.iter().find(|(name, _)| name == field.name) else {
+        return Ok(datum);
+    };
+    let filtered = std::process::Command::new("sh")
+        .args(["-c", command])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(datum.value.as_bytes())?;
+            }
+            child.wait_with_output()
+        });
+    if let Ok(output) = filtered {
+        if output.status.success() {
+            datum.value = String::from_utf8_lossy(&output.stdout).trim().to_owned();
+        }
+    }
+    Ok(datum)
+}
@@ -1,17 +1,156 @@
+mod catalog;
+mod config;
 mod data;
+mod fields;
+mod png;
+mod qr;
+mod render;
+mod svg;
+mod watch;
 
-use crate::data::{get_colours, get_hostname, get_os, get_shell, get_uptime};
+use std::io::IsTerminal;
+
+use crate::config::{config_dir, Config};
+use crate::data::{check_update, get_plugins, Style};
+use crate::fields::{fetch, find_field, PLUGIN};
+use crate::png::render_png;
+use crate::qr::render_qr;
+use crate::render::{anonymise, render, render_json, typewrite, Fetched};
+use crate::svg::render_svg;
+use crate::watch::watch;
 
 fn main() {
-    for datum in [get_hostname(), get_os(), get_shell(), get_uptime()]
-        .into_iter()
-        .flatten()
-    {
-        println!("{datum}");
+    let config = Config::load().unwrap_or_else(|error| {
+        eprintln!("orhfetch: {error}");
+        std::process::exit(2);
+    });
+    if config.version {
+        match option_env!("ORHFETCH_COMMIT") {
+            Some(commit) => println!("orhfetch {} ({commit})", env!("CARGO_PKG_VERSION")),
+            None => println!("orhfetch {}", env!("CARGO_PKG_VERSION")),
+        }
+        return;
+    }
+    if config.dump_config {
+        print!("{}", config.to_toml());
+        return;
+    }
+    let style = config.style().unwrap_or_else(|error| {
+        eprintln!("orhfetch: {error}");
+        std::process::exit(2);
+    });
+
+    let mut timings = Vec::new();
+    let mut data: Vec<Fetched> = config
+        .fields
+        .iter()
+        .filter_map(|name| find_field(name))
+        .filter_map(|field| {
+            let start = std::time::Instant::now();
+            let datum = fetch(&config, field);
+            timings.push((field.name, start.elapsed(), datum.is_ok()));
+            Some((field, datum.ok()?))
+        })
+        .collect();
+
+    if config.plugins {
+        if let Ok(dir) = config_dir() {
+            let start = std::time::Instant::now();
+            let plugins = get_plugins(&dir.join("plugins"));
+            timings.push((PLUGIN.name, start.elapsed(), true));
+            data.extend(plugins.into_iter().map(|datum| (&PLUGIN, datum)));
+        }
+    }
+
+    // checked once here, since each stale check sets off another download
+    let latest = config.check_updates.then(check_update).and_then(Result::ok);
+
+    if config.svg.is_some() || config.png.is_some() {
+        // an image is its own terminal, so it always gets colours and icons,
+        // though only foregrounds and so no stripe
+        let style = Style {
+            colour: true,
+            icons: true,
+            stripe: false,
+            ..style.clone()
+        };
+        let lines = render(&config, &style, &data, latest.as_deref());
+        let written = config
+            .svg
+            .iter()
+            .map(|path| std::fs::write(path, render_svg(&lines)))
+            .chain(
+                config
+                    .png
+                    .iter()
+                    .map(|path| std::fs::write(path, render_png(&lines))),
+            )
+            .collect::<std::io::Result<()>>();
+        if let Err(error) = written {
+            eprintln!("orhfetch: {error}");
+            std::process::exit(1);
+        }
+    } else if let Some(interval) = config.watch {
+        watch(&config, &style, data, latest.as_deref(), interval);
+    } else if config.json {
+        println!("{}", render_json(&config, &data, None));
+    } else if config.animate && std::io::stdout().is_terminal() {
+        // a failed write means there is no one left to watch
+        let _ = typewrite(&render(&config, &style, &data, latest.as_deref()));
+    } else {
+        for line in render(&config, &style, &data, latest.as_deref()) {
+            println!("{line}");
+        }
+    }
+
+    if let Some(field) = config.qr.as_deref().and_then(find_field) {
+        // the value already shown, scrubbed the same way, or fetched now if it was not
+        let shown = data
+            .iter()
+            .find(|(shown, _)| core::ptr::eq(*shown, field))
+            .map(|(_, datum)| Ok(datum.clone()))
+            .unwrap_or_else(|| fetch(&config, field));
+        let value = shown.map(|datum| {
+            if config.anon {
+                anonymise(&[(field, datum)])
+                    .remove(0)
+                    .1
+                    .text(&style.language)
+            } else {
+                datum.text(&style.language)
+            }
+        });
+        match value.and_then(|value| render_qr(&value)) {
+            Ok(code) => code
+                .iter()
+                .for_each(|line| println!("{}{line}", style.prefix)),
+            Err(error) => {
+                eprintln!("orhfetch: {}: {error}", field.name);
+                std::process::exit(1);
+            }
+        }
     }
 
-    let colours = get_colours();
-    println!("{}\n{}", colours.0, colours.1);
+    if config.timings {
+        for (name, elapsed, fetched) in timings {
+            eprintln!(
+                "{name:<16}{:>9.1}ms{}",
+                elapsed.as_secs_f64() * 1000.0,
+                if fetched { "" } else { "  (failed)" }
+            );
+        }
+    }
 
-    println!();
+    let missing: Vec<&str> = config
+        .critical
+        .iter()
+        .map(String::as_str)
+        .filter(|&name| !data.iter().any(|(field, _)| field.name == name))
+        // one left out of the fetch is still checked, just not shown
+        .filter(|&name| find_field(name).is_none_or(|field| fetch(&config, field).is_err()))
+        .collect();
+    if !missing.is_empty() {
+        eprintln!("orhfetch: missing critical {}", missing.join(", "));
+        std::process::exit(1);
+    }
 }
@@ -0,0 +1,225 @@
+use crate::render::ansi_spans;
+
+/* # png output */
+
+/// the public domain font8x8 glyphs for printable ascii, a row per byte with the
+/// leftmost pixel in the lowest bit
+const FONT: [[u8; 8]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    [0x18, 0x3c, 0x3c, 0x18, 0x18, 0x00, 0x18, 0x00],
+    [0x36, 0x36, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    [0x36, 0x36, 0x7f, 0x36, 0x7f, 0x36, 0x36, 0x00],
+    [0x0c, 0x3e, 0x03, 0x1e, 0x30, 0x1f, 0x0c, 0x00],
+    [0x00, 0x63, 0x33, 0x18, 0x0c, 0x66, 0x63, 0x00],
+    [0x1c, 0x36, 0x1c, 0x6e, 0x3b, 0x33, 0x6e, 0x00],
+    [0x06, 0x06, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00],
+    [0x18, 0x0c, 0x06, 0x06, 0x06, 0x0c, 0x18, 0x00],
+    [0x06, 0x0c, 0x18, 0x18, 0x18, 0x0c, 0x06, 0x00],
+    [0x00, 0x66, 0x3c, 0xff, 0x3c, 0x66, 0x00, 0x00],
+    [0x00, 0x0c, 0x0c, 0x3f, 0x0c, 0x0c, 0x00, 0x00],
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0c, 0x0c, 0x06],
+    [0x00, 0x00, 0x00, 0x3f, 0x00, 0x00, 0x00, 0x00],
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0c, 0x0c, 0x00],
+    [0x60, 0x30, 0x18, 0x0c, 0x06, 0x03, 0x01, 0x00],
+    [0x3e, 0x63, 0x73, 0x7b, 0x6f, 0x67, 0x3e, 0x00],
+    [0x0c, 0x0e, 0x0c, 0x0c, 0x0c, 0x0c, 0x3f, 0x00],
+    [0x1e, 0x33, 0x30, 0x1c, 0x06, 0x33, 0x3f, 0x00],
+    [0x1e, 0x33, 0x30, 0x1c, 0x30, 0x33, 0x1e, 0x00],
+    [0x38, 0x3c, 0x36, 0x33, 0x7f, 0x30, 0x78, 0x00],
+    [0x3f, 0x03, 0x1f, 0x30, 0x30, 0x33, 0x1e, 0x00],
+    [0x1c, 0x06, 0x03, 0x1f, 0x33, 0x33, 0x1e, 0x00],
+    [0x3f, 0x33, 0x30, 0x18, 0x0c, 0x0c, 0x0c, 0x00],
+    [0x1e, 0x33, 0x33, 0x1e, 0x33, 0x33, 0x1e, 0x00],
+    [0x1e, 0x33, 0x33, 0x3e, 0x30, 0x18, 0x0e, 0x00],
+    [0x00, 0x0c, 0x0c, 0x00, 0x00, 0x0c, 0x0c, 0x00],
+    [0x00, 0x0c, 0x0c, 0x00, 0x00, 0x0c, 0x0c, 0x06],
+    [0x18, 0x0c, 0x06, 0x03, 0x06, 0x0c, 0x18, 0x00],
+    [0x00, 0x00, 0x3f, 0x00, 0x00, 0x3f, 0x00, 0x00],
+    [0x06, 0x0c, 0x18, 0x30, 0x18, 0x0c, 0x06, 0x00],
+    [0x1e, 0x33, 0x30, 0x18, 0x0c, 0x00, 0x0c, 0x00],
+    [0x3e, 0x63, 0x7b, 0x7b, 0x7b, 0x03, 0x1e, 0x00],
+    [0x0c, 0x1e, 0x33, 0x33, 0x3f, 0x33, 0x33, 0x00],
+    [0x3f, 0x66, 0x66, 0x3e, 0x66, 0x66, 0x3f, 0x00],
+    [0x3c, 0x66, 0x03, 0x03, 0x03, 0x66, 0x3c, 0x00],
+    [0x1f, 0x36, 0x66, 0x66, 0x66, 0x36, 0x1f, 0x00],
+    [0x7f, 0x46, 0x16, 0x1e, 0x16, 0x46, 0x7f, 0x00],
+    [0x7f, 0x46, 0x16, 0x1e, 0x16, 0x06, 0x0f, 0x00],
+    [0x3c, 0x66, 0x03, 0x03, 0x73, 0x66, 0x7c, 0x00],
+    [0x33, 0x33, 0x33, 0x3f, 0x33, 0x33, 0x33, 0x00],
+    [0x1e, 0x0c, 0x0c, 0x0c, 0x0c, 0x0c, 0x1e, 0x00],
+    [0x78, 0x30, 0x30, 0x30, 0x33, 0x33, 0x1e, 0x00],
+    [0x67, 0x66, 0x36, 0x1e, 0x36, 0x66, 0x67, 0x00],
+    [0x0f, 0x06, 0x06, 0x06, 0x46, 0x66, 0x7f, 0x00],
+    [0x63, 0x77, 0x7f, 0x7f, 0x6b, 0x63, 0x63, 0x00],
+    [0x63, 0x67, 0x6f, 0x7b, 0x73, 0x63, 0x63, 0x00],
+    [0x1c, 0x36, 0x63, 0x63, 0x63, 0x36, 0x1c, 0x00],
+    [0x3f, 0x66, 0x66, 0x3e, 0x06, 0x06, 0x0f, 0x00],
+    [0x1e, 0x33, 0x33, 0x33, 0x3b, 0x1e, 0x38, 0x00],
+    [0x3f, 0x66, 0x66, 0x3e, 0x36, 0x66, 0x67, 0x00],
+    [0x1e, 0x33, 0x07, 0x0e, 0x38, 0x33, 0x1e, 0x00],
+    [0x3f, 0x2d, 0x0c, 0x0c, 0x0c, 0x0c, 0x1e, 0x00],
+    [0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x3f, 0x00],
+    [0x33, 0x33, 0x33, 0x33, 0x33, 0x1e, 0x0c, 0x00],
+    [0x63, 0x63, 0x63, 0x6b, 0x7f, 0x77, 0x63, 0x00],
+    [0x63, 0x63, 0x36, 0x1c, 0x1c, 0x36, 0x63, 0x00],
+    [0x33, 0x33, 0x33, 0x1e, 0x0c, 0x0c, 0x1e, 0x00],
+    [0x7f, 0x63, 0x31, 0x18, 0x4c, 0x66, 0x7f, 0x00],
+    [0x1e, 0x06, 0x06, 0x06, 0x06, 0x06, 0x1e, 0x00],
+    [0x03, 0x06, 0x0c, 0x18, 0x30, 0x60, 0x40, 0x00],
+    [0x1e, 0x18, 0x18, 0x18, 0x18, 0x18, 0x1e, 0x00],
+    [0x08, 0x1c, 0x36, 0x63, 0x00, 0x00, 0x00, 0x00],
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff],
+    [0x0c, 0x0c, 0x18, 0x00, 0x00, 0x00, 0x00, 0x00],
+    [0x00, 0x00, 0x1e, 0x30, 0x3e, 0x33, 0x6e, 0x00],
+    [0x07, 0x06, 0x06, 0x3e, 0x66, 0x66, 0x3b, 0x00],
+    [0x00, 0x00, 0x1e, 0x33, 0x03, 0x33, 0x1e, 0x00],
+    [0x38, 0x30, 0x30, 0x3e, 0x33, 0x33, 0x6e, 0x00],
+    [0x00, 0x00, 0x1e, 0x33, 0x3f, 0x03, 0x1e, 0x00],
+    [0x1c, 0x36, 0x06, 0x0f, 0x06, 0x06, 0x0f, 0x00],
+    [0x00, 0x00, 0x6e, 0x33, 0x33, 0x3e, 0x30, 0x1f],
+    [0x07, 0x06, 0x36, 0x6e, 0x66, 0x66, 0x67, 0x00],
+    [0x0c, 0x00, 0x0e, 0x0c, 0x0c, 0x0c, 0x1e, 0x00],
+    [0x30, 0x00, 0x30, 0x30, 0x30, 0x33, 0x33, 0x1e],
+    [0x07, 0x06, 0x66, 0x36, 0x1e, 0x36, 0x67, 0x00],
+    [0x0e, 0x0c, 0x0c, 0x0c, 0x0c, 0x0c, 0x1e, 0x00],
+    [0x00, 0x00, 0x33, 0x7f, 0x7f, 0x6b, 0x63, 0x00],
+    [0x00, 0x00, 0x1f, 0x33, 0x33, 0x33, 0x33, 0x00],
+    [0x00, 0x00, 0x1e, 0x33, 0x33, 0x33, 0x1e, 0x00],
+    [0x00, 0x00, 0x3b, 0x66, 0x66, 0x3e, 0x06, 0x0f],
+    [0x00, 0x00, 0x6e, 0x33, 0x33, 0x3e, 0x30, 0x78],
+    [0x00, 0x00, 0x3b, 0x6e, 0x66, 0x06, 0x0f, 0x00],
+    [0x00, 0x00, 0x3e, 0x03, 0x1e, 0x30, 0x1f, 0x00],
+    [0x08, 0x0c, 0x3e, 0x0c, 0x0c, 0x2c, 0x18, 0x00],
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x33, 0x6e, 0x00],
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x1e, 0x0c, 0x00],
+    [0x00, 0x00, 0x63, 0x6b, 0x7f, 0x7f, 0x36, 0x00],
+    [0x00, 0x00, 0x63, 0x36, 0x1c, 0x36, 0x63, 0x00],
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x3e, 0x30, 0x1f],
+    [0x00, 0x00, 0x3f, 0x19, 0x0c, 0x26, 0x3f, 0x00],
+    [0x38, 0x0c, 0x0c, 0x07, 0x0c, 0x0c, 0x38, 0x00],
+    [0x18, 0x18, 0x18, 0x00, 0x18, 0x18, 0x18, 0x00],
+    [0x07, 0x0c, 0x0c, 0x38, 0x0c, 0x0c, 0x07, 0x00],
+    [0x6e, 0x3b, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+];
+
+const CELL_WIDTH: usize = 8;
+const CELL_HEIGHT: usize = 12;
+const SCALE: usize = 2;
+const PADDING: usize = 16;
+
+const BACKGROUND: [u8; 3] = [0x1e, 0x1e, 0x1e];
+const FOREGROUND: [u8; 3] = [0xd4, 0xd4, 0xd4];
+
+/// rows of a character cell, drawing the box and block characters the fetch uses
+/// and a small square for anything else outside ascii
+fn glyph(character: char) -> [u8; 8] {
+    match character {
+        ' '..='~' => FONT[character as usize - 0x20],
+        '\u{2500}' => [0x00, 0x00, 0x00, 0xff, 0x00, 0x00, 0x00, 0x00],
+        '\u{2502}' => [0x08; 8],
+        '\u{251c}' => [0x08, 0x08, 0x08, 0xf8, 0x08, 0x08, 0x08, 0x08],
+        '\u{2514}' => [0x08, 0x08, 0x08, 0xf8, 0x00, 0x00, 0x00, 0x00],
+        '\u{2588}' => [0xff; 8],
+        '\u{2591}' => [0x55, 0x00, 0xaa, 0x00, 0x55, 0x00, 0xaa, 0x00],
+        _ => [0x00, 0x7e, 0x7e, 0x7e, 0x7e, 0x7e, 0x7e, 0x00],
+    }
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffff_u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut low, mut high) = (1_u32, 0_u32);
+    for &byte in bytes {
+        low = (low + u32::from(byte)) % 65521;
+        high = (high + low) % 65521;
+    }
+    (high << 16) | low
+}
+
+/// a zlib stream of uncompressed deflate blocks, which every decoder accepts
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut stream = vec![0x78, 0x01];
+    let mut blocks = data.chunks(0xffff).peekable();
+    while let Some(block) = blocks.next() {
+        let length = block.len() as u16;
+        stream.push(u8::from(blocks.peek().is_none()));
+        stream.extend(length.to_le_bytes());
+        stream.extend((!length).to_le_bytes());
+        stream.extend(block);
+    }
+    stream.extend(adler32(data).to_be_bytes());
+    stream
+}
+
+fn push_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+/// the rendered fetch rasterised in a bitmap font, escapes turned into pixel colours
+pub fn render_png(lines: &[String]) -> Vec<u8> {
+    let spans: Vec<_> = lines.iter().map(|line| ansi_spans(line)).collect();
+    let columns = spans
+        .iter()
+        .map(|line| line.iter().map(|(_, text)| text.chars().count()).sum())
+        .max()
+        .unwrap_or(0);
+
+    let width = 2 * PADDING + columns * CELL_WIDTH * SCALE;
+    let height = 2 * PADDING + lines.len() * CELL_HEIGHT * SCALE;
+    let mut pixels = vec![BACKGROUND; width * height];
+
+    for (row, line) in spans.iter().enumerate() {
+        let top = PADDING + (row * CELL_HEIGHT + (CELL_HEIGHT - 8) / 2) * SCALE;
+        let characters = line
+            .iter()
+            .flat_map(|(colour, text)| text.chars().map(move |character| (colour, character)));
+        for (column, (colour, character)) in characters.enumerate() {
+            let left = PADDING + column * CELL_WIDTH * SCALE;
+            let colour = colour.unwrap_or(FOREGROUND);
+            for (y, bits) in glyph(character).iter().enumerate() {
+                for x in (0..8).filter(|x| bits >> x & 1 == 1) {
+                    for (dy, dx) in (0..SCALE).flat_map(|dy| (0..SCALE).map(move |dx| (dy, dx))) {
+                        pixels[(top + y * SCALE + dy) * width + left + x * SCALE + dx] = colour;
+                    }
+                }
+            }
+        }
+    }
+
+    // every scanline is led by its filter type, none
+    let mut scanlines = Vec::with_capacity((width * 3 + 1) * height);
+    for row in pixels.chunks(width) {
+        scanlines.push(0);
+        scanlines.extend(row.iter().flatten());
+    }
+
+    let mut header = Vec::new();
+    header.extend((width as u32).to_be_bytes());
+    header.extend((height as u32).to_be_bytes());
+    // eight bit rgb, deflate, no interlacing
+    header.extend([8, 2, 0, 0, 0]);
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    push_chunk(&mut png, b"IHDR", &header);
+    push_chunk(&mut png, b"IDAT", &zlib_stored(&scanlines));
+    push_chunk(&mut png, b"IEND", &[]);
+    png
+}
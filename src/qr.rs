@@ -0,0 +1,521 @@
+/* # qr codes */
+
+/// error correction codewords in each block at level l, for versions 1 to 10
+const EC_PER_BLOCK: [usize; 10] = [7, 10, 15, 20, 26, 18, 20, 24, 30, 18];
+const BLOCKS: [usize; 10] = [1, 1, 1, 1, 1, 2, 2, 2, 2, 4];
+
+/// level l in the two bits the format information gives it
+const LEVEL_BITS: u32 = 0b01;
+
+/// product of two elements of gf(256) under the qr polynomial
+fn gf_multiply(a: u8, b: u8) -> u8 {
+    let mut product = 0_u16;
+    for bit in (0..8).rev() {
+        product = (product << 1) ^ ((product >> 7) * 0x11d);
+        product ^= ((b >> bit) & 1) as u16 * a as u16;
+    }
+    product as u8
+}
+
+/// reed-solomon error correction codewords for one block
+fn error_correction(data: &[u8], length: usize) -> Vec<u8> {
+    let mut generator = vec![0_u8; length - 1];
+    generator.push(1);
+    let mut root = 1_u8;
+    for _ in 0..length {
+        for index in 0..length {
+            generator[index] = gf_multiply(generator[index], root);
+            if index + 1 < length {
+                generator[index] ^= generator[index + 1];
+            }
+        }
+        root = gf_multiply(root, 0x02);
+    }
+
+    let mut remainder = vec![0_u8; length];
+    for &byte in data {
+        let factor = byte ^ remainder.remove(0);
+        remainder.push(0);
+        for (slot, &coefficient) in remainder.iter_mut().zip(&generator) {
+            *slot ^= gf_multiply(coefficient, factor);
+        }
+    }
+    remainder
+}
+
+fn alignment_positions(version: usize) -> Vec<usize> {
+    if version == 1 {
+        return Vec::new();
+    }
+    let count = version / 7 + 2;
+    let step = (version * 4 + count * 2 + 1) / (count * 2 - 2) * 2;
+    let mut positions: Vec<usize> = (0..count - 1)
+        .map(|index| 17 + version * 4 - 7 - index * step)
+        .collect();
+    positions.push(6);
+    positions.reverse();
+    positions
+}
+
+/// modules left over for codewords once every pattern is drawn
+fn data_modules(version: usize) -> usize {
+    let mut modules = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let count = version / 7 + 2;
+        modules -= (25 * count - 10) * count - 55;
+        if version >= 7 {
+            modules -= 36;
+        }
+    }
+    modules
+}
+
+/// `text` in byte mode, padded and split into blocks, interleaved with their
+/// error correction as the codewords go onto the grid
+fn encode(text: &[u8], version: usize) -> Vec<u8> {
+    let total = data_modules(version) / 8;
+    let (blocks, ec) = (BLOCKS[version - 1], EC_PER_BLOCK[version - 1]);
+    let capacity = total - blocks * ec;
+
+    let mut bits = Vec::new();
+    let mut push = |value: usize, length: usize| {
+        bits.extend((0..length).rev().map(|bit| (value >> bit) & 1 == 1));
+    };
+    push(0b0100, 4);
+    push(text.len(), if version < 10 { 8 } else { 16 });
+    for &byte in text {
+        push(byte as usize, 8);
+    }
+    let terminator = (capacity * 8 - bits.len()).min(4);
+    bits.extend(core::iter::repeat_n(false, terminator));
+    while bits.len() % 8 != 0 {
+        bits.push(false);
+    }
+    let mut data: Vec<u8> = bits
+        .chunks(8)
+        .map(|byte| byte.iter().fold(0, |acc, &bit| (acc << 1) | bit as u8))
+        .collect();
+    for pad in [0xec, 0x11].into_iter().cycle() {
+        if data.len() == capacity {
+            break;
+        }
+        data.push(pad);
+    }
+
+    // the last few blocks take one codeword more than the first
+    let short = total / blocks - ec;
+    let long_blocks = capacity % blocks;
+    let mut rest = data.as_slice();
+    let chunks: Vec<&[u8]> = (0..blocks)
+        .map(|index| {
+            let length = short + usize::from(index >= blocks - long_blocks);
+            let (chunk, remaining) = rest.split_at(length);
+            rest = remaining;
+            chunk
+        })
+        .collect();
+    let corrections: Vec<Vec<u8>> = chunks
+        .iter()
+        .map(|chunk| error_correction(chunk, ec))
+        .collect();
+
+    let mut codewords = Vec::with_capacity(total);
+    for index in 0..=short {
+        codewords.extend(chunks.iter().filter_map(|chunk| chunk.get(index)));
+    }
+    for index in 0..ec {
+        codewords.extend(corrections.iter().map(|correction| correction[index]));
+    }
+    codewords
+}
+
+struct Grid {
+    size: usize,
+    dark: Vec<bool>,
+    /// finder, timing, alignment and information modules, which masks leave alone
+    reserved: Vec<bool>,
+}
+
+impl Grid {
+    fn set(&mut self, x: usize, y: usize, dark: bool) {
+        self.dark[y * self.size + x] = dark;
+        self.reserved[y * self.size + x] = true;
+    }
+
+    fn get(&self, x: usize, y: usize) -> bool {
+        self.dark[y * self.size + x]
+    }
+
+    fn draw_finder(&mut self, centre_x: usize, centre_y: usize) {
+        for dy in -4_isize..=4 {
+            for dx in -4_isize..=4 {
+                let (x, y) = (centre_x as isize + dx, centre_y as isize + dy);
+                if (0..self.size as isize).contains(&x) && (0..self.size as isize).contains(&y) {
+                    let ring = dx.abs().max(dy.abs());
+                    self.set(x as usize, y as usize, ring != 2 && ring != 4);
+                }
+            }
+        }
+    }
+
+    fn draw_alignment(&mut self, centre_x: usize, centre_y: usize) {
+        for dy in -2_isize..=2 {
+            for dx in -2_isize..=2 {
+                let ring = dx.abs().max(dy.abs());
+                self.set(
+                    (centre_x as isize + dx) as usize,
+                    (centre_y as isize + dy) as usize,
+                    ring != 1,
+                );
+            }
+        }
+    }
+
+    fn draw_format(&mut self, mask: u32) {
+        let data = (LEVEL_BITS << 3) | mask;
+        let mut remainder = data;
+        for _ in 0..10 {
+            remainder = (remainder << 1) ^ ((remainder >> 9) * 0x537);
+        }
+        let bits = ((data << 10) | remainder) ^ 0x5412;
+        let bit = |index: usize| (bits >> index) & 1 == 1;
+
+        for index in 0..6 {
+            self.set(8, index, bit(index));
+        }
+        self.set(8, 7, bit(6));
+        self.set(8, 8, bit(7));
+        self.set(7, 8, bit(8));
+        for index in 9..15 {
+            self.set(14 - index, 8, bit(index));
+        }
+        for index in 0..8 {
+            self.set(self.size - 1 - index, 8, bit(index));
+        }
+        for index in 8..15 {
+            self.set(8, self.size - 15 + index, bit(index));
+        }
+        self.set(8, self.size - 8, true);
+    }
+
+    fn draw_version(&mut self, version: usize) {
+        if version < 7 {
+            return;
+        }
+        let mut remainder = version as u32;
+        for _ in 0..12 {
+            remainder = (remainder << 1) ^ ((remainder >> 11) * 0x1f25);
+        }
+        let bits = ((version as u32) << 12) | remainder;
+        for index in 0..18 {
+            let dark = (bits >> index) & 1 == 1;
+            let (a, b) = (self.size - 11 + index % 3, index / 3);
+            self.set(a, b, dark);
+            self.set(b, a, dark);
+        }
+    }
+
+    fn new(version: usize) -> Self {
+        let size = 17 + version * 4;
+        let mut grid = Self {
+            size,
+            dark: vec![false; size * size],
+            reserved: vec![false; size * size],
+        };
+        for index in 0..size {
+            grid.set(6, index, index % 2 == 0);
+            grid.set(index, 6, index % 2 == 0);
+        }
+        grid.draw_finder(3, 3);
+        grid.draw_finder(size - 4, 3);
+        grid.draw_finder(3, size - 4);
+        let positions = alignment_positions(version);
+        let last = positions.len().saturating_sub(1);
+        for (i, &x) in positions.iter().enumerate() {
+            for (j, &y) in positions.iter().enumerate() {
+                if ![(0, 0), (0, last), (last, 0)].contains(&(i, j)) {
+                    grid.draw_alignment(x, y);
+                }
+            }
+        }
+        // reserved for now, drawn for real once the mask is chosen
+        grid.draw_format(0);
+        grid.draw_version(version);
+        grid
+    }
+
+    /// codewords snaking up and down two columns at a time from the bottom right
+    fn draw_codewords(&mut self, codewords: &[u8]) {
+        let mut index = 0;
+        let mut right = self.size - 1;
+        loop {
+            if right == 6 {
+                right = 5;
+            }
+            for vertical in 0..self.size {
+                for x in [right, right - 1] {
+                    let upward = (right + 1) & 2 == 0;
+                    let y = if upward {
+                        self.size - 1 - vertical
+                    } else {
+                        vertical
+                    };
+                    if !self.reserved[y * self.size + x] && index < codewords.len() * 8 {
+                        self.dark[y * self.size + x] =
+                            (codewords[index / 8] >> (7 - index % 8)) & 1 == 1;
+                        index += 1;
+                    }
+                }
+            }
+            if right < 2 {
+                break;
+            }
+            right -= 2;
+        }
+    }
+
+    fn apply_mask(&mut self, mask: u32) {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                let flip = match mask {
+                    0 => (x + y) % 2 == 0,
+                    1 => y % 2 == 0,
+                    2 => x % 3 == 0,
+                    3 => (x + y) % 3 == 0,
+                    4 => (x / 3 + y / 2) % 2 == 0,
+                    5 => x * y % 2 + x * y % 3 == 0,
+                    6 => (x * y % 2 + x * y % 3) % 2 == 0,
+                    _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+                };
+                if flip && !self.reserved[y * self.size + x] {
+                    self.dark[y * self.size + x] ^= true;
+                }
+            }
+        }
+    }
+
+    /// the standard's score for how hard a masked grid is to read, lower being better
+    fn penalty(&self) -> usize {
+        let size = self.size;
+        let mut penalty = 0;
+        let lines = (0..size).flat_map(|line| {
+            [
+                (0..size).map(|x| self.get(x, line)).collect::<Vec<bool>>(),
+                (0..size).map(|y| self.get(line, y)).collect::<Vec<bool>>(),
+            ]
+        });
+        let finder_like = [
+            true, false, true, true, true, false, true, false, false, false, false,
+        ];
+        for line in lines {
+            let mut run = 1;
+            for index in 1..=size {
+                if index < size && line[index] == line[index - 1] {
+                    run += 1;
+                } else {
+                    if run >= 5 {
+                        penalty += run - 2;
+                    }
+                    run = 1;
+                }
+            }
+            for window in line.windows(finder_like.len()) {
+                if window == finder_like || window.iter().rev().eq(finder_like.iter()) {
+                    penalty += 40;
+                }
+            }
+        }
+        for y in 0..size - 1 {
+            for x in 0..size - 1 {
+                let colour = self.get(x, y);
+                if colour == self.get(x + 1, y)
+                    && colour == self.get(x, y + 1)
+                    && colour == self.get(x + 1, y + 1)
+                {
+                    penalty += 3;
+                }
+            }
+        }
+        let dark = self.dark.iter().filter(|&&dark| dark).count();
+        let total = size * size;
+        let deviation = (dark * 20).abs_diff(total * 10);
+        penalty + deviation / total * 10
+    }
+}
+
+/// `bytes` laid out at level l in the smallest version that holds them, still unmasked
+fn unmasked(bytes: &[u8]) -> Result<Grid, Box<dyn std::error::Error>> {
+    let version = (1..=10)
+        .find(|&version| {
+            let capacity =
+                data_modules(version) / 8 - BLOCKS[version - 1] * EC_PER_BLOCK[version - 1];
+            let header = if version < 10 { 2 } else { 3 };
+            bytes.len() + header <= capacity
+        })
+        .ok_or_else(|| simple_error::simple_error!("too long for a qr code"))?;
+    let codewords = encode(bytes, version);
+
+    let mut grid = Grid::new(version);
+    grid.draw_codewords(&codewords);
+    Ok(grid)
+}
+
+/// `text` as a qr code at level l, two rows of modules to a line of half blocks,
+/// light modules drawn in the foreground so it reads on a dark terminal
+pub fn render_qr(text: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    const QUIET: usize = 2;
+
+    let mut grid = unmasked(text.as_bytes())?;
+    let (mask, _) = (0..8)
+        .map(|mask| {
+            let mut masked = Grid {
+                size: grid.size,
+                dark: grid.dark.clone(),
+                reserved: grid.reserved.clone(),
+            };
+            masked.apply_mask(mask);
+            masked.draw_format(mask);
+            (mask, masked.penalty())
+        })
+        .min_by_key(|&(_, penalty)| penalty)
+        .unwrap_or_default();
+    grid.apply_mask(mask);
+    grid.draw_format(mask);
+
+    let size = grid.size;
+    let light = |x: isize, y: isize| {
+        !(0..size as isize).contains(&x)
+            || !(0..size as isize).contains(&y)
+            || !grid.get(x as usize, y as usize)
+    };
+    let span = -(QUIET as isize)..(size + QUIET) as isize;
+    Ok(span
+        .clone()
+        .step_by(2)
+        .map(|y| {
+            span.clone()
+                .map(|x| match (light(x, y), light(x, y + 1)) {
+                    (true, true) => '\u{2588}',
+                    (true, false) => '\u{2580}',
+                    (false, true) => '\u{2584}',
+                    (false, false) => ' ',
+                })
+                .collect()
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// hello world at version 1-m, the worked example most tutorials use
+    #[test]
+    fn error_correction_block() {
+        let data = [
+            32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236, 17,
+        ];
+        assert_eq!(
+            error_correction(&data, 10),
+            [196, 35, 39, 119, 235, 215, 231, 226, 93, 23]
+        );
+    }
+
+    /// the grid under mask 0, a row of `#` and `.` to each line of modules
+    fn rows(text: &str) -> Vec<String> {
+        let mut grid = unmasked(text.as_bytes()).unwrap();
+        grid.apply_mask(0);
+        grid.draw_format(0);
+        (0..grid.size)
+            .map(|y| {
+                (0..grid.size)
+                    .map(|x| if grid.get(x, y) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect()
+    }
+
+    const VERSION_1: [&str; 21] = [
+        "#######...#.#.#######",
+        "#.....#.....#.#.....#",
+        "#.###.#.#.#...#.###.#",
+        "#.###.#.....#.#.###.#",
+        "#.###.#..#.##.#.###.#",
+        "#.....#..###..#.....#",
+        "#######.#.#.#.#######",
+        "........#.#..........",
+        "###.#####.#.###...#..",
+        "#....#.#.###.#####..#",
+        "###...######..###..##",
+        "#.#..#..#..###..#...#",
+        "#.#..##..###..#.#..#.",
+        "........#.#...#######",
+        "#######.##..#..##.###",
+        "#.....#.###...##...#.",
+        "#.###.#.#...#.###....",
+        "#.###.#..###.#..#..#.",
+        "#.###.#.#..#.####...#",
+        "#.....#.######.....#.",
+        "#######.#..#.####..##",
+    ];
+
+    #[test]
+    fn version_1() {
+        assert_eq!(rows("orhfetch"), VERSION_1);
+    }
+
+    /// big enough to need version 7, the first to carry version information
+    const VERSION_7: [&str; 45] = [
+        "#######...#.###.#...#..#.##.######..#.#######",
+        "#.....#..#.###...........###..#....#..#.....#",
+        "#.###.#.#..###..##.##.###.##...#...#..#.###.#",
+        "#.###.#..#...#..#...####.##..#.....##.#.###.#",
+        "#.###.#..####..##..######.###.##.####.#.###.#",
+        "#.....#..####.##.#.##...###..##.##....#.....#",
+        "#######.#.#.#.#.#.#.#.#.#.#.#.#.#.#.#.#######",
+        "........#.##.###.#..#...#..###.#####.........",
+        "###.#######..#..#########.#.#..##.##.##...#..",
+        "..##.#..###..#..#.#.#...#....#.###.....###.##",
+        "##.##########.#..#...####......##..#.#..###.#",
+        ".#.#.#.##....#..###....###.###.####.#...#....",
+        ".#.#.###########..#.#.....###.####....####.##",
+        "#..##......####..#####..#..##..###.....#.#.##",
+        "..##.###..#...###.#...####.##..##..##...#.###",
+        "#.##...#.#.#....#......#..#.##..##.###.##..#.",
+        "..#...#.#.#...#####.....##.##.###.#....###.#.",
+        "#.#.##..#......#..##.##.#..##...#......#.####",
+        "..######.#.###.##.#..####....#.#........#..##",
+        "#.##....##.#..##.#..........######...####..#.",
+        "..########...##..##.#####.###..##.#.######...",
+        "...##...#####.#..####...#...#..#....#...##.##",
+        "....#.#.##.#.#.######.#.#..#.#..#..##.#.#####",
+        ".##.#...#..#.##....##...#...#..######...#..#.",
+        "....#####..###..##########.##.###..#######..#",
+        "###.#...#.##.#..###.####.#.....#...###.#.####",
+        "..#.###....####..##.###.....#...##..####.####",
+        "#.#.#..#.#.#.##.###..#.#.####..###..###..#.#.",
+        ".####.####...###.##..####.#####.#...##.....##",
+        "#####..####..##..##.###....#...##..#..##...##",
+        "#.##..###...#.#.....#.#.....#..#.#...###.##.#",
+        "####....###....##..#..#..#.######...#.###....",
+        "#..#..###...#.#####.###.#.###.#.###..##....##",
+        ".##.#..##.#.#..#.##.###.#..#....#..#.....#..#",
+        "....#.#.###..#####..##...#.#....#...#.##..###",
+        ".####..##.#.#..#..##..#....##.####...##.#..#.",
+        "#..##.#..###.##..##.#######.#####...#####..#.",
+        "........###...#...###...##..#...#..##...#####",
+        "#######.###.##..#.###.#.#...##.##..##.#.#####",
+        "#.....#.#..#.###..###...#..##...#.#.#...#..#.",
+        "#.###.#.######..#.#.#####.###############....",
+        "#.###.#...##.#..##..#..#...#.#.#....#..##..#.",
+        "#.###.#.#..##.#....##.......#...##.###.##.#..",
+        "#.....#.####.##.##.#######..##..#.##....#..#.",
+        "#######.###...#...#.##.##.###.####..#...#..##",
+    ];
+
+    #[test]
+    fn version_7() {
+        let text = "the quick brown fox jumps over the lazy dog ".repeat(4);
+        assert_eq!(rows(&text[..140]), VERSION_7);
+    }
+}
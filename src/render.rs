@@ -0,0 +1,544 @@
+use crate::catalog::translate;
+use crate::config::{Config, Layout};
+use crate::data::{format_data, format_hostname, get_colours, Datum, HostnameLayout, Style, RESET};
+use crate::fields::{Field, PLUGIN};
+
+/// a field alongside what it fetched
+pub type Fetched = (&'static Field, Datum);
+
+/* # escapes */
+
+/// rgb stand-ins for the sixteen terminal colours where there is no terminal to ask
+pub const ANSI_RGB: [[u8; 3]; 16] = [
+    [0x00, 0x00, 0x00],
+    [0xcd, 0x31, 0x31],
+    [0x0d, 0xbc, 0x79],
+    [0xe5, 0xe5, 0x10],
+    [0x24, 0x72, 0xc8],
+    [0xbc, 0x3f, 0xbc],
+    [0x11, 0xa8, 0xcd],
+    [0xe5, 0xe5, 0xe5],
+    [0x66, 0x66, 0x66],
+    [0xf1, 0x4c, 0x4c],
+    [0x23, 0xd1, 0x8b],
+    [0xf5, 0xf5, 0x43],
+    [0x3b, 0x8e, 0xea],
+    [0xd6, 0x70, 0xd6],
+    [0x29, 0xb8, 0xdb],
+    [0xe5, 0xe5, 0xe5],
+];
+
+fn xterm_rgb(index: u8) -> [u8; 3] {
+    match index {
+        0..=15 => ANSI_RGB[index as usize],
+        16..=231 => {
+            let level = |step: u8| if step == 0 { 0 } else { 55 + step * 40 };
+            let cube = index - 16;
+            [level(cube / 36), level(cube / 6 % 6), level(cube % 6)]
+        }
+        _ => [8 + (index - 232) * 10; 3],
+    }
+}
+
+/// a line of output split into runs of text and the foreground colour they are in,
+/// `None` being the default
+pub fn ansi_spans(line: &str) -> Vec<(Option<[u8; 3]>, String)> {
+    let mut spans = Vec::new();
+    let mut colour = None;
+    let mut text = String::new();
+    let mut rest = line;
+
+    while let Some((start, end)) = rest
+        .find("\x1b[")
+        .and_then(|start| Some((start, rest[start..].find('m')?)))
+    {
+        text.push_str(&rest[..start]);
+        if !text.is_empty() {
+            spans.push((colour, core::mem::take(&mut text)));
+        }
+
+        let codes: Vec<u8> = rest[start + 2..start + end]
+            .split(';')
+            .map(|code| code.parse().unwrap_or(0))
+            .collect();
+        let mut codes = codes.iter().copied();
+        while let Some(code) = codes.next() {
+            colour = match code {
+                0 | 39 => None,
+                30..=37 => Some(ANSI_RGB[(code - 30) as usize]),
+                90..=97 => Some(ANSI_RGB[(code - 82) as usize]),
+                38 => match codes.next() {
+                    Some(5) => codes.next().map(xterm_rgb),
+                    Some(2) => Some([
+                        codes.next().unwrap_or(0),
+                        codes.next().unwrap_or(0),
+                        codes.next().unwrap_or(0),
+                    ]),
+                    _ => colour,
+                },
+                // backgrounds are not drawn, but their arguments must not pass for codes
+                48 => {
+                    let arguments = match codes.next() {
+                        Some(5) => 1,
+                        Some(2) => 3,
+                        _ => 0,
+                    };
+                    codes.by_ref().take(arguments).for_each(drop);
+                    colour
+                }
+                _ => colour,
+            };
+        }
+        rest = &rest[start + end + 1..];
+    }
+
+    text.push_str(rest);
+    if !text.is_empty() {
+        spans.push((colour, text));
+    }
+    spans
+}
+
+/// how many columns a line takes up once its escapes are gone
+fn visible_width(line: &str) -> usize {
+    ansi_spans(line)
+        .iter()
+        .map(|(_, text)| text.chars().count())
+        .sum()
+}
+
+/// every line padded on the left to end in the same column
+fn align_right(lines: &mut [String]) {
+    let width = lines
+        .iter()
+        .map(|line| visible_width(line))
+        .max()
+        .unwrap_or(0);
+    for line in lines {
+        *line = format!("{}{line}", " ".repeat(width - visible_width(line)));
+    }
+}
+
+/// the art to the left of the lines, each row of it padded out to the widest
+fn beside_logo(logo: &[String], lines: &[String], colour: bool) -> Vec<String> {
+    let plain =
+        |art: &str| -> String { ansi_spans(art).into_iter().map(|(_, text)| text).collect() };
+    let width = logo.iter().map(|art| visible_width(art)).max().unwrap_or(0);
+    (0..logo.len().max(lines.len()))
+        .map(|row| {
+            let art = logo.get(row).map_or("", String::as_str);
+            let line = lines.get(row).map_or("", String::as_str);
+            let padding = " ".repeat(width - visible_width(art));
+            if colour {
+                format!("{art}{RESET}{padding}  {line}")
+            } else {
+                format!("{}{padding}  {line}", plain(art))
+            }
+        })
+        .collect()
+}
+
+/* # anonymising */
+
+/// every ip address in `value` swapped for a placeholder
+fn redact_addresses(value: &str) -> String {
+    let mut redacted = String::new();
+    let mut run = String::new();
+    let flush = |run: &mut String, redacted: &mut String| {
+        redacted.push_str(match run.parse::<std::net::IpAddr>() {
+            Ok(std::net::IpAddr::V4(_)) => "x.x.x.x",
+            Ok(std::net::IpAddr::V6(_)) => "x:x:x:x",
+            Err(_) => run,
+        });
+        run.clear();
+    };
+    for c in value.chars() {
+        if c.is_ascii_hexdigit() || c == '.' || c == ':' {
+            run.push(c);
+        } else {
+            flush(&mut run, &mut redacted);
+            redacted.push(c);
+        }
+    }
+    flush(&mut run, &mut redacted);
+    redacted
+}
+
+/// the namespace kept but the pod, which may just be the host's name, swapped for a placeholder
+fn redact_pod(value: &str) -> String {
+    match value.split_once('/') {
+        Some((namespace, _)) => format!("{namespace}/pod"),
+        None => "pod".to_owned(),
+    }
+}
+
+/// the nearest of the sixteen terminal colours to `rgb`
+fn nearest_ansi(rgb: [u8; 3]) -> usize {
+    (0..ANSI_RGB.len())
+        .min_by_key(|&index| {
+            ANSI_RGB[index]
+                .iter()
+                .zip(rgb)
+                .map(|(&a, b)| (i32::from(a) - i32::from(b)).pow(2))
+                .sum::<i32>()
+        })
+        .unwrap_or(0)
+}
+
+/// a line with each foreground colour swapped for its slot in `palette`,
+/// fixed rgb and 256 colour codes going to whichever slot is closest
+pub fn recolour(line: &str, palette: &[String]) -> String {
+    let mut recoloured = String::new();
+    let mut rest = line;
+
+    while let Some((start, end)) = rest
+        .find("\x1b[")
+        .and_then(|start| Some((start, rest[start..].find('m')?)))
+    {
+        recoloured.push_str(&rest[..start]);
+        let codes: Vec<u8> = rest[start + 2..start + end]
+            .split(';')
+            .map(|code| code.parse().unwrap_or(0))
+            .collect();
+        let mut codes = codes.iter().copied();
+        while let Some(code) = codes.next() {
+            let slot = match code {
+                30..=37 => Some(usize::from(code - 30)),
+                90..=97 => Some(usize::from(code - 82)),
+                38 => match codes.next() {
+                    Some(5) => codes.next().map(|index| match index {
+                        0..=15 => usize::from(index),
+                        _ => nearest_ansi(xterm_rgb(index)),
+                    }),
+                    Some(2) => Some(nearest_ansi([
+                        codes.next().unwrap_or(0),
+                        codes.next().unwrap_or(0),
+                        codes.next().unwrap_or(0),
+                    ])),
+                    _ => None,
+                },
+                _ => None,
+            };
+            match slot.and_then(|slot| palette.get(slot)) {
+                Some(colour) => recoloured.push_str(colour),
+                None if code == 38 => {}
+                // backgrounds keep their arguments with them
+                None if code == 48 => {
+                    let mut background = vec![code.to_string()];
+                    let arguments = match codes.next() {
+                        Some(5) => {
+                            background.push("5".to_owned());
+                            1
+                        }
+                        Some(2) => {
+                            background.push("2".to_owned());
+                            3
+                        }
+                        _ => 0,
+                    };
+                    background.extend(codes.by_ref().take(arguments).map(|code| code.to_string()));
+                    recoloured.push_str(&format!("\x1b[{}m", background.join(";")));
+                }
+                None => recoloured.push_str(&format!("\x1b[{code}m")),
+            }
+        }
+        rest = &rest[start + end + 1..];
+    }
+
+    recoloured.push_str(rest);
+    recoloured
+}
+
+/* # layouts */
+
+fn is_hostname(field: &Field) -> bool {
+    field.name == "hostname"
+}
+
+fn render_plain(data: &[Fetched], style: &Style, hostname: HostnameLayout) -> Vec<String> {
+    data.iter()
+        .flat_map(|(field, datum)| {
+            if is_hostname(field) {
+                format_hostname(datum, style, hostname)
+            } else {
+                vec![format_data(datum, style)]
+            }
+        })
+        .collect()
+}
+
+/// fields sectioned by group under accented headers, hung off tree branches,
+/// with the hostname heading the lot wherever it was placed
+fn render_tree(data: &[Fetched], style: &Style, hostname: HostnameLayout) -> Vec<String> {
+    let (named, data): (Vec<&Fetched>, Vec<&Fetched>) =
+        data.iter().partition(|(field, _)| is_hostname(field));
+    let mut lines: Vec<String> = named
+        .iter()
+        .flat_map(|(_, datum)| format_hostname(datum, style, hostname))
+        .collect();
+
+    let mut groups: Vec<_> = data.iter().map(|(field, _)| field.group).collect();
+    groups.sort();
+    groups.dedup();
+
+    let (accent, reset) = if style.colour {
+        (style.accent.as_str(), RESET)
+    } else {
+        ("", "")
+    };
+    let branch = |glyph: &str| Style {
+        prefix: format!("{}{glyph} ", style.prefix),
+        ..style.clone()
+    };
+    let (middle, last) = (branch("\u{251c}\u{2500}"), branch("\u{2514}\u{2500}"));
+
+    for group in groups {
+        lines.push(format!(
+            "{}{accent}{}{reset}",
+            style.prefix,
+            translate(&style.language, group.name())
+        ));
+        let members: Vec<&Datum> = data
+            .iter()
+            .filter(|(field, _)| field.group == group)
+            .map(|(_, datum)| datum)
+            .collect();
+        for (index, datum) in members.iter().enumerate() {
+            let branch = if index + 1 == members.len() {
+                &last
+            } else {
+                &middle
+            };
+            lines.push(format_data(datum, branch));
+        }
+    }
+    lines
+}
+
+/// `name: value` lines sorted by field name, bare of colour and icons, for diffing
+fn render_canonical(data: &[Fetched]) -> Vec<String> {
+    let mut lines: Vec<String> = data
+        .iter()
+        .map(|(field, datum)| {
+            // a plugin goes by its own label, the field only saying it is a plugin
+            let name = match &datum.label {
+                Some(label) if core::ptr::eq(*field, &PLUGIN) => label,
+                _ => field.name,
+            };
+            format!("{name}: {}", datum.bare("en"))
+        })
+        .collect();
+    lines.sort();
+    lines
+}
+
+/// every value on one line between accented brackets and bars
+fn render_badge(data: &[Fetched], style: &Style) -> String {
+    let (accent, reset) = if style.colour {
+        (style.accent.as_str(), RESET)
+    } else {
+        ("", "")
+    };
+    let values: Vec<String> = data
+        .iter()
+        .map(|(_, datum)| datum.text(&style.language))
+        .collect();
+    format!(
+        "{accent}[{reset} {} {accent}]{reset}",
+        values.join(&format!(" {accent}|{reset} "))
+    )
+}
+
+/// the fetch with sensitive fields scrubbed, when `--anon` asks for it
+pub fn anonymise(data: &[Fetched]) -> Vec<Fetched> {
+    data.iter()
+        .map(|(field, datum)| {
+            let value = match field.name {
+                _ if !field.sensitive => datum.value.clone(),
+                "kubernetes" => redact_pod(&datum.value),
+                _ => redact_addresses(&datum.value),
+            };
+            (
+                *field,
+                Datum {
+                    icon: datum.icon,
+                    label: datum.label.clone(),
+                    value,
+                    suffix: datum.suffix,
+                    words: datum.words.clone(),
+                    usage: datum.usage,
+                    status: datum.status,
+                },
+            )
+        })
+        .collect()
+}
+
+/* # json */
+
+fn escape_json(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// the fetch as a single line json object keyed by field name, plugins gathered
+/// into an object by their labels, stamped with unix `time` when given one
+pub fn render_json(config: &Config, data: &[Fetched], time: Option<u64>) -> String {
+    let anonymised;
+    let data = if config.anon {
+        anonymised = anonymise(data);
+        &anonymised
+    } else {
+        data
+    };
+
+    let mut members = Vec::new();
+    if let Some(time) = time {
+        members.push(format!("\"time\":{time}"));
+    }
+    let plugins: Vec<String> = data
+        .iter()
+        .filter(|(field, _)| core::ptr::eq(*field, &PLUGIN))
+        .map(|(_, datum)| {
+            format!(
+                "\"{}\":\"{}\"",
+                escape_json(datum.label.as_deref().unwrap_or_default()),
+                escape_json(&datum.bare("en"))
+            )
+        })
+        .collect();
+    members.extend(
+        data.iter()
+            .filter(|(field, _)| !core::ptr::eq(*field, &PLUGIN))
+            .map(|(field, datum)| {
+                format!("\"{}\":\"{}\"", field.name, escape_json(&datum.bare("en")))
+            }),
+    );
+    if !plugins.is_empty() {
+        members.push(format!("\"{}\":{{{}}}", PLUGIN.name, plugins.join(",")));
+    }
+    format!("{{{}}}", members.join(","))
+}
+
+/* # animation */
+
+/// prints the lines a character at a time, escapes going out whole so they never show
+pub fn typewrite(lines: &[String]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut stdout = std::io::stdout();
+    for line in lines {
+        let mut rest = line.as_str();
+        while let Some(c) = rest.chars().next() {
+            let length = match rest.strip_prefix("\x1b[").and_then(|codes| codes.find('m')) {
+                Some(end) => end + 3,
+                None => c.len_utf8(),
+            };
+            write!(stdout, "{}", &rest[..length])?;
+            stdout.flush()?;
+            if length == c.len_utf8() && !c.is_whitespace() {
+                std::thread::sleep(std::time::Duration::from_millis(4));
+            }
+            rest = &rest[length..];
+        }
+        writeln!(stdout)?;
+    }
+    Ok(())
+}
+
+/* # whole fetch */
+
+/// the fetch as printed, mentioning `latest` when a newer release was found
+pub fn render(
+    config: &Config,
+    style: &Style,
+    data: &[Fetched],
+    latest: Option<&str>,
+) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    let anonymised;
+    let data = if config.anon {
+        anonymised = anonymise(data);
+        &anonymised
+    } else {
+        data
+    };
+
+    if config.canonical {
+        return render_canonical(data);
+    }
+
+    if config.badge {
+        return vec![render_badge(data, style)];
+    }
+
+    if config.values_only {
+        let plain = Style {
+            colour: false,
+            icons: false,
+            prefix: String::new(),
+            ..style.clone()
+        };
+        return data
+            .iter()
+            .flat_map(|(field, datum)| {
+                if is_hostname(field) {
+                    format_hostname(datum, &plain, config.hostname)
+                } else {
+                    vec![datum.bare("en")]
+                }
+            })
+            .collect();
+    }
+
+    lines.extend(match config.layout {
+        Layout::Plain => render_plain(data, style, config.hostname),
+        Layout::Tree => render_tree(data, style, config.hostname),
+    });
+
+    if let Some(latest) = latest {
+        lines.push(format!(
+            "{}({}: {latest})",
+            style.prefix,
+            translate(&style.language, "update available")
+        ));
+    }
+
+    if config.colours && style.colour {
+        let colours = get_colours(style, config.colour_labels);
+        lines.push(colours.0);
+        lines.push(colours.1);
+    }
+
+    if let Some(footer) = &config.footer {
+        lines.push(format!(
+            "{}{}",
+            style.prefix,
+            footer
+                .replace("%version%", env!("CARGO_PKG_VERSION"))
+                .replace(
+                    "%commit%",
+                    option_env!("ORHFETCH_COMMIT").unwrap_or_default()
+                )
+        ));
+    }
+
+    if style.rtl {
+        align_right(&mut lines);
+    }
+    if !style.logo.is_empty() {
+        lines = beside_logo(&style.logo, &lines, style.colour);
+    }
+    lines.push(String::new());
+    lines
+}
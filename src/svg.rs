@@ -0,0 +1,56 @@
+use crate::render::ansi_spans;
+
+/* # svg output */
+
+const FONT_SIZE: f64 = 14.0;
+const CHARACTER_WIDTH: f64 = FONT_SIZE * 0.6;
+const LINE_HEIGHT: f64 = FONT_SIZE * 1.4;
+const PADDING: f64 = 16.0;
+
+const BACKGROUND: &str = "#1e1e1e";
+const FOREGROUND: &str = "#d4d4d4";
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// the rendered fetch as a standalone svg, escapes turned into fills
+pub fn render_svg(lines: &[String]) -> String {
+    let spans: Vec<_> = lines.iter().map(|line| ansi_spans(line)).collect();
+    let columns = spans
+        .iter()
+        .map(|line| line.iter().map(|(_, text)| text.chars().count()).sum())
+        .max()
+        .unwrap_or(0);
+
+    let width = 2.0 * PADDING + columns as f64 * CHARACTER_WIDTH;
+    let height = 2.0 * PADDING + lines.len() as f64 * LINE_HEIGHT;
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width:.0}\" height=\"{height:.0}\" \
+         font-family=\"monospace\" font-size=\"{FONT_SIZE}\">\n\
+         <rect width=\"100%\" height=\"100%\" rx=\"6\" fill=\"{BACKGROUND}\"/>\n"
+    );
+
+    for (row, line) in spans.iter().enumerate() {
+        let y = PADDING + (row as f64 + 0.8) * LINE_HEIGHT;
+        svg.push_str(&format!(
+            "<text x=\"{PADDING}\" y=\"{y:.1}\" xml:space=\"preserve\">"
+        ));
+        for (colour, text) in line {
+            let fill = match colour {
+                Some([red, green, blue]) => format!("#{red:02x}{green:02x}{blue:02x}"),
+                None => FOREGROUND.to_owned(),
+            };
+            svg.push_str(&format!(
+                "<tspan fill=\"{fill}\">{}</tspan>",
+                escape_xml(text)
+            ));
+        }
+        svg.push_str("</text>\n");
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
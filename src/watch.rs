@@ -0,0 +1,157 @@
+use std::io::{IsTerminal, Write};
+
+use nix::sys::termios::{tcgetattr, tcsetattr, LocalFlags, SetArg, Termios};
+
+use crate::config::Config;
+use crate::data::Style;
+use crate::fields::fetch;
+use crate::render::{render, render_json, Fetched};
+
+/* # watch mode */
+
+const CLEAR: &str = "\x1b[H\x1b[2J";
+
+/// fetches the fields again, only the volatile ones unless `everything`,
+/// keeping the last value of any that now fail
+fn refresh(config: &Config, data: &mut [Fetched], everything: bool) {
+    for (field, datum) in data.iter_mut() {
+        if everything || field.volatile {
+            if let Ok(fresh) = fetch(config, field) {
+                *datum = fresh;
+            }
+        }
+    }
+}
+
+/// the whole fetch from the top of a cleared screen
+fn redraw(lines: &[String]) -> String {
+    let mut screen = CLEAR.to_owned();
+    for line in lines {
+        screen.push_str(line);
+        screen.push('\n');
+    }
+    screen
+}
+
+/// just the lines that changed, each rewritten where it already sits,
+/// leaving the cursor below the fetch
+fn update(shown: &[String], lines: &[String]) -> String {
+    if shown.len() != lines.len() {
+        return redraw(lines);
+    }
+    let mut screen = String::new();
+    for (row, (old, new)) in shown.iter().zip(lines).enumerate() {
+        if old != new {
+            screen.push_str(&format!("\x1b[{};1H\x1b[2K{new}", row + 1));
+        }
+    }
+    screen.push_str(&format!("\x1b[{};1H", lines.len() + 1));
+    screen
+}
+
+/// a json line per refresh, stamped with when it was fetched
+fn stream_json(config: &Config, mut data: Vec<Fetched>, interval: core::time::Duration) -> ! {
+    let mut stdout = std::io::stdout();
+    loop {
+        let time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+        let _ = writeln!(stdout, "{}", render_json(config, &data, Some(time)));
+        let _ = stdout.flush();
+        std::thread::sleep(interval);
+        refresh(config, &mut data, !config.in_place);
+    }
+}
+
+/* ## keybinds */
+
+/// turns off line buffering, echo and signals on the terminal, handing back how it was
+fn raw_mode() -> Option<Termios> {
+    if !std::io::stdin().is_terminal() {
+        return None;
+    }
+    let original = tcgetattr(0).ok()?;
+    let mut raw = original.clone();
+    raw.local_flags
+        .remove(LocalFlags::ICANON | LocalFlags::ECHO | LocalFlags::ISIG);
+    tcsetattr(0, SetArg::TCSANOW, &raw).ok()?;
+    Some(original)
+}
+
+/// the next key pressed before `deadline`, or just a sleep without a terminal to read
+fn next_key(deadline: std::time::Instant, keys: bool) -> Option<u8> {
+    let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+    if !keys {
+        std::thread::sleep(remaining);
+        return None;
+    }
+    let mut fds = [nix::poll::PollFd::new(0, nix::poll::PollFlags::POLLIN)];
+    let timeout = i32::try_from(remaining.as_millis()).unwrap_or(i32::MAX);
+    if nix::poll::poll(&mut fds, timeout).ok()? == 0 {
+        return None;
+    }
+    let mut key = [0];
+    match nix::unistd::read(0, &mut key) {
+        Ok(1) => Some(key[0]),
+        _ => None,
+    }
+}
+
+/// reprints the fetch every `interval` until interrupted, or on a terminal until `q`,
+/// the digits 1 to 9 hiding and showing the field in that place
+pub fn watch(
+    config: &Config,
+    style: &Style,
+    mut data: Vec<Fetched>,
+    latest: Option<&str>,
+    interval: core::time::Duration,
+) -> ! {
+    if config.json {
+        stream_json(config, data, interval);
+    }
+    let terminal = raw_mode();
+    let mut hidden = vec![false; data.len()];
+    let visible = |data: &[Fetched], hidden: &[bool]| -> Vec<Fetched> {
+        data.iter()
+            .zip(hidden)
+            .filter(|(_, &hidden)| !hidden)
+            .map(|(fetched, _)| fetched.clone())
+            .collect()
+    };
+
+    let mut stdout = std::io::stdout();
+    let mut shown = render(config, style, &data, latest);
+    let _ = write!(stdout, "{}", redraw(&shown));
+    let _ = stdout.flush();
+
+    let mut deadline = std::time::Instant::now() + interval;
+    loop {
+        match next_key(deadline, terminal.is_some()) {
+            // ctrl-c arrives as a byte now signals are off
+            Some(b'q' | 0x03) => {
+                if let Some(original) = &terminal {
+                    let _ = tcsetattr(0, SetArg::TCSANOW, original);
+                }
+                std::process::exit(0);
+            }
+            Some(key @ b'1'..=b'9') => match hidden.get_mut(usize::from(key - b'1')) {
+                Some(toggled) => *toggled = !*toggled,
+                None => continue,
+            },
+            Some(_) => continue,
+            None => {
+                refresh(config, &mut data, !config.in_place);
+                deadline += interval;
+            }
+        }
+        let lines = render(config, style, &visible(&data, &hidden), latest);
+        let screen = if config.in_place {
+            update(&shown, &lines)
+        } else {
+            redraw(&lines)
+        };
+        let _ = write!(stdout, "{screen}");
+        let _ = stdout.flush();
+        shown = lines;
+    }
+}